@@ -0,0 +1,132 @@
+use num::PrimInt;
+use std::cmp;
+use std::ops::Range;
+
+// A set of integers stored as sorted, disjoint, non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(value: Range<T>) -> Self {
+        Self::from_ranges([value])
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    pub fn from_ranges(ranges: impl IntoIterator<Item = Range<T>>) -> Self {
+        let mut ranges = ranges
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<T>> = Vec::new();
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = cmp::max(last.end, r.end),
+                _ => merged.push(r),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Number of integers in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |total, r| total + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::one())
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.ranges.iter().any(|r| r.contains(x))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while (i < self.ranges.len()) & (j < other.ranges.len()) {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = cmp::max(a.start, b.start);
+            let end = cmp::min(a.end, b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for r in self.ranges.iter() {
+            let mut start = r.start;
+            for o in other
+                .ranges
+                .iter()
+                .filter(|o| (o.end > r.start) & (o.start < r.end))
+            {
+                if o.start > start {
+                    ranges.push(start..o.start);
+                }
+                start = cmp::max(start, o.end);
+            }
+            if start < r.end {
+                ranges.push(start..r.end);
+            }
+        }
+        Self { ranges }
+    }
+
+    // Split into the values less than `x` and those greater than or equal to `x`.
+    pub fn split_at(&self, x: T) -> (Self, Self) {
+        let below = Self::from(T::min_value()..x);
+        (self.intersection(&below), self.difference(&below))
+    }
+
+    // Move each part of the set covered by a `(source, dest_start)` rule so that
+    // `source.start` lands on `dest_start`. Values not covered by any rule are left as
+    // they are. If rules overlap, the first matching rule wins.
+    pub fn map_ranges(&self, rules: &[(Range<T>, T)]) -> Self {
+        let mut remaining = self.clone();
+        let mut mapped = Vec::new();
+        for (source, dest_start) in rules.iter() {
+            let source = Self::from(source.clone());
+            for r in remaining.intersection(&source).ranges {
+                let offset = r.start - source.ranges[0].start;
+                mapped.push((*dest_start + offset)..(*dest_start + offset + (r.end - r.start)));
+            }
+            remaining = remaining.difference(&source);
+        }
+        remaining.union(&Self::from_ranges(mapped))
+    }
+}
@@ -1,5 +1,8 @@
 mod data;
-mod math_utils;
+pub mod intervals;
+pub mod math_utils;
+pub mod memo;
+pub mod search;
 pub mod solutions;
 
 use thiserror::Error;
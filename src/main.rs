@@ -1,6 +1,9 @@
 mod new_day;
 
-use aoc_2023::{run_all, run_day};
+use aoc_2023::{
+    run_all, run_day,
+    solutions::{day07, day12, day14},
+};
 use clap::{Parser, Subcommand};
 use std::time::Instant;
 /// Simple program to greet a person
@@ -29,6 +32,48 @@ enum Command {
             help = "Do not run any puzzles (to estimate start-up time)."
         )]
         empty: bool,
+        #[arg(short, long, help = "Log extra details, such as cache statistics.")]
+        verbose: bool,
+    },
+    #[command(about = "Show how each day 7 Camel Cards hand was ranked.")]
+    Cards {
+        #[arg(default_value_t = String::from("puzzle-input"), help="Directory with input data files.")]
+        data_dir: String,
+        #[arg(short, long, help = "Use the part 2 rules with jokers wild.")]
+        jokers: bool,
+    },
+    #[command(about = "Show the day 12 spring arrangements.")]
+    Springs {
+        #[arg(default_value_t = String::from("puzzle-input"), help="Directory with input data files.")]
+        data_dir: String,
+        #[arg(
+            short,
+            long,
+            default_value_t = 1,
+            help = "Number of copies to unfold each row into."
+        )]
+        unfold: usize,
+        #[arg(
+            short,
+            long,
+            default_value_t = 0,
+            help = "Number of arrangements to print for each row."
+        )]
+        show: usize,
+    },
+    #[command(about = "Explore the day 14 spin cycle loop.")]
+    Spin {
+        #[arg(default_value_t = String::from("puzzle-input"), help="Directory with input data files.")]
+        data_dir: String,
+        #[arg(
+            short,
+            long,
+            default_value_t = 1000000000,
+            help = "Number of spin cycles to find the load after."
+        )]
+        cycles: usize,
+        #[arg(short, long, help = "Draw the platform after this many spin cycles.")]
+        render: Option<usize>,
     },
     #[command()]
     New {
@@ -46,11 +91,28 @@ fn main() {
                 Err(e) => panic!("Failed to create new file templates:\n  {:?}.", e),
             };
         }
+        Command::Cards { data_dir, jokers } => day07::print_report(&data_dir, jokers),
+        Command::Springs {
+            data_dir,
+            unfold,
+            show,
+        } => day12::print_arrangements(&data_dir, unfold, show),
+        Command::Spin {
+            data_dir,
+            cycles,
+            render,
+        } => day14::explore_cycles(&data_dir, cycles, render),
         Command::Run {
             data_dir,
             day,
             empty,
+            verbose,
         } => {
+            let mut logger = env_logger::Builder::from_default_env();
+            if verbose {
+                logger.filter_level(log::LevelFilter::Info);
+            }
+            logger.init();
             let start = Instant::now();
             if empty {
                 println!("Empty run.");
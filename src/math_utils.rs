@@ -1,4 +1,5 @@
-use num::integer;
+use num::{integer, CheckedAdd, CheckedMul, Num};
+use thiserror::Error;
 
 // Algorithm source: https://www.geeksforgeeks.org/lcm-of-given-array-elements/
 pub fn lcm(a: Vec<u64>) -> u64 {
@@ -8,3 +9,91 @@ pub fn lcm(a: Vec<u64>) -> u64 {
     }
     lcm
 }
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MathError {
+    #[error("Cannot fit a polynomial to an empty sequence.")]
+    EmptySequence,
+    #[error("Sequence is not polynomial (differences never reach zero).")]
+    NotPolynomial,
+    #[error("Extrapolated value does not fit in the number type.")]
+    Overflow,
+}
+
+// A sequence generated by a polynomial, found by repeatedly differencing the values
+// until a row of zeros is reached. Values are extrapolated with Newton's forward
+// difference formula, so only exact arithmetic is required (e.g. `i64`, `i128` or
+// `Ratio<i64>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolynomialSequence<T> {
+    // First value of each row of the difference table, up to the polynomial's degree.
+    leading_diffs: Vec<T>,
+    len: usize,
+}
+
+impl<T> PolynomialSequence<T>
+where
+    T: Clone + Num + CheckedAdd + CheckedMul + From<i64>,
+{
+    pub fn fit(seq: &[T]) -> Result<Self, MathError> {
+        if seq.is_empty() {
+            return Err(MathError::EmptySequence);
+        }
+        let mut leading_diffs = Vec::new();
+        let mut row = seq.to_vec();
+        while !row.iter().all(|x| x.is_zero()) {
+            leading_diffs.push(row[0].clone());
+            row = row
+                .windows(2)
+                .map(|a| a[1].clone() - a[0].clone())
+                .collect();
+            // Only a row of zeros confirms the sequence is polynomial.
+            if row.is_empty() {
+                return Err(MathError::NotPolynomial);
+            }
+        }
+        if leading_diffs.is_empty() {
+            leading_diffs.push(T::zero());
+        }
+        Ok(Self {
+            leading_diffs,
+            len: seq.len(),
+        })
+    }
+
+    pub fn degree(&self) -> usize {
+        self.leading_diffs.len() - 1
+    }
+
+    // Value of the sequence at index `i`, where the first fitted value is index 0, or an
+    // error if it or any term of the sum does not fit in `T`.
+    pub fn value_at(&self, i: i64) -> Result<T, MathError> {
+        // Generalised binomial coefficient C(i, k), exact at every step.
+        let mut binom = T::one();
+        let mut value = T::zero();
+        for (k, d) in self.leading_diffs.iter().enumerate() {
+            let term = d.checked_mul(&binom).ok_or(MathError::Overflow)?;
+            value = value.checked_add(&term).ok_or(MathError::Overflow)?;
+            // Stop before a coefficient that is never used and could overflow.
+            if k + 1 < self.leading_diffs.len() {
+                // C(i, k + 1) = C(i, k) * (i - k) / (k + 1). With the common factor of
+                // (i - k) and (k + 1) removed, what is left of (k + 1) divides C(i, k),
+                // so dividing first keeps the intermediate no larger than the result.
+                let (num, den) = (i - k as i64, k as i64 + 1);
+                let g = integer::gcd(num, den);
+                binom = (binom / T::from(den / g))
+                    .checked_mul(&T::from(num / g))
+                    .ok_or(MathError::Overflow)?;
+            }
+        }
+        Ok(value)
+    }
+
+    pub fn predict_ahead(&self, k: usize) -> Result<T, MathError> {
+        self.value_at(self.len as i64 - 1 + k as i64)
+    }
+
+    pub fn predict_behind(&self, k: usize) -> Result<T, MathError> {
+        self.value_at(-(k as i64))
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses", self.hits, self.misses)
+    }
+}
+
+// Memoisation table owned by a solver, so that results do not leak between runs.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    table: HashMap<K, V>,
+    stats: CacheStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            table: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Look up a key, recording a hit or a miss.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.table.get(key).cloned();
+        match value {
+            Some(_) => self.stats.hits += 1,
+            None => self.stats.misses += 1,
+        };
+        value
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        self.table.insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    // Drop all stored results and reset the statistics.
+    pub fn clear(&mut self) {
+        self.table.clear();
+        self.stats = CacheStats::default();
+    }
+}
@@ -0,0 +1,183 @@
+use num::{Signed, Zero};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+#[derive(Clone, PartialEq, Eq)]
+struct Frontier<S, C> {
+    state: S,
+    cost: C,
+    priority: C,
+}
+
+// Reversed so that the `BinaryHeap` pops the lowest priority first.
+impl<S: Eq, C: Ord> Ord for Frontier<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.cost.cmp(&self.cost))
+    }
+}
+
+impl<S: Eq, C: Ord> PartialOrd for Frontier<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn reconstruct_path<S: Clone + Eq + Hash>(came_from: &HashMap<S, S>, end: S) -> Vec<S> {
+    let mut path = Vec::from_iter([end]);
+    while let Some(prev) = came_from.get(path.last().unwrap()) {
+        path.push(prev.clone());
+    }
+    path.reverse();
+    path
+}
+
+// Manhattan distance between two `(row, column)` positions.
+pub fn manhattan<T: Signed + Copy>(a: (T, T), b: (T, T)) -> T {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+// A* search from any of `starts` to the first state satisfying `is_goal`.
+// `neighbors` returns the states reachable from a state along with the cost of the
+// move. `heuristic` must never over-estimate the remaining cost to a goal. Returns
+// the total cost and the path of states (including the start and goal), or `None`
+// if no goal is reachable.
+pub fn astar<S, C, FN, IN, FH, FG>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let mut best = HashMap::<S, C>::new();
+    let mut came_from = HashMap::<S, S>::new();
+    let mut queue = BinaryHeap::new();
+    for state in starts {
+        best.insert(state.clone(), C::zero());
+        queue.push(Frontier {
+            priority: heuristic(&state),
+            state,
+            cost: C::zero(),
+        });
+    }
+
+    while let Some(Frontier { state, cost, .. }) = queue.pop() {
+        if best.get(&state).is_some_and(|b| b < &cost) {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((cost, reconstruct_path(&came_from, state)));
+        }
+        for (next_state, step_cost) in neighbors(&state) {
+            let next_cost = cost + step_cost;
+            if best.get(&next_state).is_some_and(|b| b <= &next_cost) {
+                continue;
+            }
+            best.insert(next_state.clone(), next_cost);
+            came_from.insert(next_state.clone(), state.clone());
+            queue.push(Frontier {
+                priority: next_cost + heuristic(&next_state),
+                state: next_state,
+                cost: next_cost,
+            });
+        }
+    }
+    None
+}
+
+// Dijkstra's algorithm; A* without a heuristic.
+pub fn dijkstra<S, C, FN, IN, FG>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: FN,
+    is_goal: FG,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(starts, neighbors, |_| C::zero(), is_goal)
+}
+
+// Breadth-first search over unit-cost moves. Returns the number of steps and the
+// path of states, or `None` if no goal is reachable.
+pub fn bfs<S, FN, IN, FG>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: FN,
+    mut is_goal: FG,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FG: FnMut(&S) -> bool,
+{
+    let mut came_from = HashMap::<S, S>::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    for state in starts {
+        if seen.insert(state.clone()) {
+            queue.push_back(state);
+        }
+    }
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            let path = reconstruct_path(&came_from, state);
+            return Some((path.len() - 1, path));
+        }
+        for next_state in neighbors(&state) {
+            if seen.insert(next_state.clone()) {
+                came_from.insert(next_state.clone(), state.clone());
+                queue.push_back(next_state);
+            }
+        }
+    }
+    None
+}
+
+// Number of steps from the nearest of `starts` to every reachable state. States
+// further than `max_steps` away (if given) are not explored.
+pub fn bfs_distances<S, FN, IN>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: FN,
+    max_steps: Option<usize>,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    let mut dists = HashMap::new();
+    let mut queue = VecDeque::new();
+    for state in starts {
+        if !dists.contains_key(&state) {
+            dists.insert(state.clone(), 0);
+            queue.push_back(state);
+        }
+    }
+    while let Some(state) = queue.pop_front() {
+        let d = dists[&state];
+        if max_steps.is_some_and(|m| d >= m) {
+            continue;
+        }
+        for next_state in neighbors(&state) {
+            if !dists.contains_key(&next_state) {
+                dists.insert(next_state.clone(), d + 1);
+                queue.push_back(next_state);
+            }
+        }
+    }
+    dists
+}
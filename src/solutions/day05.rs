@@ -1,6 +1,7 @@
 use std::ops::Range;
 
 use crate::data::load;
+use crate::intervals::IntervalSet;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -8,6 +9,10 @@ use thiserror::Error;
 pub enum PuzzleErr {
     #[error("Integer parsing error: '{}'.", .0)]
     IntParseError(String),
+    #[error("Seed ranges need a start and a length, but there are {} seed numbers.", .0)]
+    OddSeedCount(usize),
+    #[error("There are no seeds to plant.")]
+    NoSeeds,
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +62,15 @@ impl Map {
         }
         *source_val
     }
+
+    fn translate_set(&self, source_vals: &IntervalSet<u32>) -> IntervalSet<u32> {
+        let rules = self
+            .ranges
+            .iter()
+            .map(|r| (r.range.clone(), r.dest_start))
+            .collect::<Vec<_>>();
+        source_vals.map_ranges(&rules)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +87,14 @@ impl Almanac {
         }
         res
     }
+
+    fn apply_maps_to_set(&self, xs: &IntervalSet<u32>) -> IntervalSet<u32> {
+        let mut res = xs.clone();
+        for map in self.maps.iter() {
+            res = map.translate_set(&res);
+        }
+        res
+    }
 }
 
 fn get_seeds(input: &str) -> Result<Vec<u32>, PuzzleErr> {
@@ -122,28 +144,25 @@ fn parse_input(input: &str) -> Result<Almanac, PuzzleErr> {
 
 pub fn puzzle_1(input: &str) -> Result<u32, PuzzleErr> {
     let almanac = parse_input(input)?;
-    Ok(almanac
+    almanac
         .seeds
         .iter()
         .map(|x| almanac.apply_maps(x))
         .min()
-        .unwrap())
+        .ok_or(PuzzleErr::NoSeeds)
 }
 
 pub fn puzzle_2(input: &str) -> Result<u32, PuzzleErr> {
     let almanac = parse_input(input)?;
-    Ok(almanac
-        .seeds
-        .windows(2)
-        .step_by(2)
-        .map(|x| {
-            (x[0]..(x[0] + x[1]))
-                .map(|y| almanac.apply_maps(&y))
-                .min()
-                .unwrap()
-        })
+    if almanac.seeds.len() % 2 != 0 {
+        return Err(PuzzleErr::OddSeedCount(almanac.seeds.len()));
+    }
+    let seeds =
+        IntervalSet::from_ranges(almanac.seeds.chunks_exact(2).map(|x| x[0]..(x[0] + x[1])));
+    almanac
+        .apply_maps_to_set(&seeds)
         .min()
-        .unwrap())
+        .ok_or(PuzzleErr::NoSeeds)
 }
 
 pub fn main(data_dir: &str) {
@@ -10,30 +10,103 @@ pub enum PuzzleErr<'a> {
     LineParsingError(&'a str),
 }
 
-struct Race {
-    duration: u64,
-    record_distance: u64,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub duration: u128,
+    pub record_distance: u128,
+}
+
+// The hold times that beat the record, `first..=last`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WinningHolds {
+    pub first: u128,
+    pub last: u128,
+}
+
+impl WinningHolds {
+    pub fn count(&self) -> u128 {
+        self.last - self.first + 1
+    }
 }
 
 impl Race {
-    fn n_ways_to_win(&self) -> u64 {
-        (1..self.duration)
-            .filter(|t| self.record_distance < (t * (self.duration - t)))
-            .collect::<Vec<_>>()
-            .len() as u64
+    pub fn new(duration: u128, record_distance: u128) -> Self {
+        Self {
+            duration,
+            record_distance,
+        }
     }
+
+    // Write the hold as `h - x` with `h = T / 2`. For even `T` it travels `h^2 - x^2`, and
+    // for odd `T` it travels `h(h + 1) - x(x + 1)`, with the mirror-image hold on the
+    // other side of the peak. It wins while `x^2` (or `x(x + 1)`) is below `R`, the peak
+    // distance minus the record, so the widest winning `x` is the integer square root of
+    // `R - 1`, less one if `x(x + 1)` overshoots. `R` can need 256 bits.
+    pub fn winning_holds(&self) -> Option<WinningHolds> {
+        let h = self.duration / 2;
+        let odd = self.duration % 2 == 1;
+        let peak = wide_mul(h, h + odd as u128);
+        let margin = wide_sub(wide_sub(peak, self.record_distance)?, 1)?;
+        let mut x = wide_isqrt(margin);
+        if odd && wide_mul(x, x + 1) > margin {
+            x -= 1;
+        }
+        Some(WinningHolds {
+            first: h - x,
+            last: h + odd as u128 + x,
+        })
+    }
+
+    pub fn n_ways_to_win(&self) -> u128 {
+        self.winning_holds().map_or(0, |w| w.count())
+    }
+}
+
+// `a * b` as `(high, low)` 128-bit words.
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    let half = u64::MAX as u128;
+    let (a_hi, a_lo, b_hi, b_lo) = (a >> 64, a & half, b >> 64, b & half);
+    let (lo_lo, lo_hi, hi_lo) = (a_lo * b_lo, a_lo * b_hi, a_hi * b_lo);
+    let mid = (lo_lo >> 64) + (lo_hi & half) + (hi_lo & half);
+    (
+        a_hi * b_hi + (lo_hi >> 64) + (hi_lo >> 64) + (mid >> 64),
+        (lo_lo & half) | (mid << 64),
+    )
+}
+
+// `a - b`, or `None` if `b` is larger.
+fn wide_sub(a: (u128, u128), b: u128) -> Option<(u128, u128)> {
+    match a.1.overflowing_sub(b) {
+        (lo, false) => Some((a.0, lo)),
+        (lo, true) => Some((a.0.checked_sub(1)?, lo)),
+    }
+}
+
+// Largest `s` with `s^2 <= v`. Roots of values above `u128::MAX` are at least 2^64, and
+// are found bit by bit from the top.
+fn wide_isqrt(v: (u128, u128)) -> u128 {
+    if v.0 == 0 {
+        return v.1.isqrt();
+    }
+    (0..128).rev().fold(0, |s, bit| {
+        let candidate = s | (1 << bit);
+        match wide_mul(candidate, candidate) <= v {
+            true => candidate,
+            false => s,
+        }
+    })
 }
 
-fn _get_nums(line: &str) -> Result<Vec<u64>, PuzzleErr> {
+fn _get_nums(line: &str) -> Result<Vec<u128>, PuzzleErr<'_>> {
     line.split(':')
         .nth(1)
         .ok_or(PuzzleErr::LineParsingError(line))?
         .split_whitespace()
-        .map(|s| s.parse::<u64>().or(Err(PuzzleErr::LineParsingError(line))))
+        .map(|s| s.parse::<u128>().or(Err(PuzzleErr::LineParsingError(line))))
         .collect::<Result<_, _>>()
 }
 
-fn parse_data1(input: &str) -> Result<Vec<Race>, PuzzleErr> {
+fn parse_data1(input: &str) -> Result<Vec<Race>, PuzzleErr<'_>> {
     let lines = input.trim().lines().collect::<Vec<_>>();
     if lines.len() != 2 {
         return Err(PuzzleErr::InputDataError("Not two lines in input."));
@@ -48,16 +121,16 @@ fn parse_data1(input: &str) -> Result<Vec<Race>, PuzzleErr> {
         .collect())
 }
 
-fn _get_num(line: &str) -> Result<u64, PuzzleErr> {
+fn _get_num(line: &str) -> Result<u128, PuzzleErr<'_>> {
     line.split(':')
         .nth(1)
         .ok_or(PuzzleErr::LineParsingError(line))?
         .replace(' ', "")
-        .parse::<u64>()
+        .parse::<u128>()
         .or(Err(PuzzleErr::LineParsingError(line)))
 }
 
-fn parse_data2(input: &str) -> Result<Race, PuzzleErr> {
+fn parse_data2(input: &str) -> Result<Race, PuzzleErr<'_>> {
     let lines = input.trim().lines().collect::<Vec<_>>();
     if lines.len() != 2 {
         return Err(PuzzleErr::InputDataError("Not two lines in input."));
@@ -68,14 +141,14 @@ fn parse_data2(input: &str) -> Result<Race, PuzzleErr> {
     })
 }
 
-pub fn puzzle_1(input: &str) -> Result<u64, PuzzleErr> {
+pub fn puzzle_1(input: &str) -> Result<u128, PuzzleErr<'_>> {
     Ok(parse_data1(input)?
         .iter()
         .map(|r| r.n_ways_to_win())
         .product())
 }
 
-pub fn puzzle_2(input: &str) -> Result<u64, PuzzleErr> {
+pub fn puzzle_2(input: &str) -> Result<u128, PuzzleErr<'_>> {
     Ok(parse_data2(input)?.n_ways_to_win())
 }
 
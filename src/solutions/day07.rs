@@ -1,263 +1,313 @@
 use crate::data::load;
-use std::{cmp::Ordering, collections::HashMap, hash::Hash, iter::zip};
+use std::{cmp::Ordering, collections::HashMap, fmt::Display};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleErr<'a> {
     #[error("Input parsing error: '{}'.", .0)]
     InputParsingError(&'a str),
-    #[error("Unrecognized card.")]
-    UnknownCard,
+    #[error("Unrecognized card: '{}'.", .0)]
+    UnknownCard(char),
+    #[error("Hand has {} cards but the rules need {}.", .0, .1)]
+    WrongHandSize(usize, usize),
+    #[error("Invalid ruleset: {}.", .0)]
+    InvalidRuleset(String),
 }
 
-trait FromStr {
-    fn from_str(s: char) -> Result<Self, PuzzleErr<'static>>
-    where
-        Self: Sized;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    High,
+    OnePair,
+    TwoPair,
+    ThreeKind,
+    FullHouse,
+    FourKind,
+    FiveKind,
 }
 
-trait Card: Clone + Copy + PartialEq + Eq + PartialOrd + Ord + core::hash::Hash + FromStr {}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Card1 {
-    N2,
-    N3,
-    N4,
-    N5,
-    N6,
-    N7,
-    N8,
-    N9,
-    T,
-    J,
-    Q,
-    K,
-    A,
+// How to order two hands of the same type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    // Compare the cards in the order they were dealt (the puzzle's rule).
+    InOrder,
+    // Compare the cards from strongest to weakest, as in poker.
+    Sorted,
 }
 
-impl Card for Card1 {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: Vec<char>,
+    pub bid: u32,
+}
 
-impl FromStr for Card1 {
-    fn from_str(s: char) -> Result<Self, PuzzleErr<'static>> {
-        match s {
-            '2' => Ok(Self::N2),
-            '3' => Ok(Self::N3),
-            '4' => Ok(Self::N4),
-            '5' => Ok(Self::N5),
-            '6' => Ok(Self::N6),
-            '7' => Ok(Self::N7),
-            '8' => Ok(Self::N8),
-            '9' => Ok(Self::N9),
-            'T' => Ok(Self::T),
-            'J' => Ok(Self::J),
-            'Q' => Ok(Self::Q),
-            'K' => Ok(Self::K),
-            'A' => Ok(Self::A),
-            _ => Err(PuzzleErr::UnknownCard),
-        }
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.cards.iter().collect::<String>())
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Card2 {
-    J,
-    N2,
-    N3,
-    N4,
-    N5,
-    N6,
-    N7,
-    N8,
-    N9,
-    T,
-    Q,
-    K,
-    A,
+// How one hand fared under a ruleset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandReport {
+    pub hand: Hand,
+    pub hand_type: HandType,
+    pub rank: u32,
+    pub winnings: u32,
+    // The card the wild cards stood in for, if the hand had any.
+    pub substitution: Option<char>,
 }
 
-impl FromStr for Card2 {
-    fn from_str(s: char) -> Result<Self, PuzzleErr<'static>> {
-        match Card1::from_str(s) {
-            Ok(Card1::N2) => Ok(Self::N2),
-            Ok(Card1::N3) => Ok(Self::N3),
-            Ok(Card1::N4) => Ok(Self::N4),
-            Ok(Card1::N5) => Ok(Self::N5),
-            Ok(Card1::N6) => Ok(Self::N6),
-            Ok(Card1::N7) => Ok(Self::N7),
-            Ok(Card1::N8) => Ok(Self::N8),
-            Ok(Card1::N9) => Ok(Self::N9),
-            Ok(Card1::T) => Ok(Self::T),
-            Ok(Card1::J) => Ok(Self::J),
-            Ok(Card1::Q) => Ok(Self::Q),
-            Ok(Card1::K) => Ok(Self::K),
-            Ok(Card1::A) => Ok(Self::A),
-            Err(e) => Err(e),
+impl Display for HandReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>5}  {}  {:<10}  {:>5}  {:>10}",
+            self.rank,
+            self.hand,
+            format!("{:?}", self.hand_type),
+            self.hand.bid,
+            self.winnings
+        )?;
+        if let Some(c) = self.substitution {
+            write!(f, "  (wild as {})", c)?;
         }
+        Ok(())
     }
 }
 
-impl Card for Card2 {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    // Cards from weakest to strongest.
+    card_order: Vec<char>,
+    wild: Option<char>,
+    hand_size: usize,
+    tie_break: TieBreak,
+}
 
-fn count<T: std::hash::Hash + std::cmp::Eq + Copy>(v: &[T]) -> HashMap<T, u32> {
-    let mut map = HashMap::new();
-    for x in v.iter() {
-        map.entry(*x).and_modify(|a| *a += 1_u32).or_insert(1_u32);
+impl Ruleset {
+    pub fn new(
+        card_order: &str,
+        wild: Option<char>,
+        hand_size: usize,
+        tie_break: TieBreak,
+    ) -> Result<Self, PuzzleErr<'static>> {
+        let card_order = card_order.chars().collect::<Vec<_>>();
+        if card_order.is_empty() {
+            return Err(PuzzleErr::InvalidRuleset("no cards".to_string()));
+        }
+        if let Some(c) = card_order
+            .iter()
+            .enumerate()
+            .find(|(i, c)| card_order[..*i].contains(c))
+            .map(|(_, c)| c)
+        {
+            return Err(PuzzleErr::InvalidRuleset(format!("'{}' listed twice", c)));
+        }
+        if let Some(w) = wild.filter(|w| !card_order.contains(w)) {
+            return Err(PuzzleErr::InvalidRuleset(format!(
+                "wild card '{}' is not in the card order",
+                w
+            )));
+        }
+        if hand_size == 0 {
+            return Err(PuzzleErr::InvalidRuleset("empty hands".to_string()));
+        }
+        Ok(Self {
+            card_order,
+            wild,
+            hand_size,
+            tie_break,
+        })
     }
-    map
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum HandType {
-    High,
-    OnePair,
-    TwoPair,
-    ThreeKind,
-    FullHouse,
-    FourKind,
-    FiveKind,
-}
+    // The rules for part 1.
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA", None, 5, TieBreak::InOrder).unwrap()
+    }
 
-#[derive(Debug, Clone)]
-struct Hand<C: Card> {
-    cards: Vec<C>,
-    bid: u32,
-}
+    // The rules for part 2: jokers are wild but the weakest card in a tie.
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", Some('J'), 5, TieBreak::InOrder).unwrap()
+    }
 
-impl<C: Card> Hand<C> {
-    fn new(cards: Vec<C>, bid: u32) -> Self {
-        Self { cards, bid }
+    fn strength(&self, card: &char) -> Result<usize, PuzzleErr<'static>> {
+        self.card_order
+            .iter()
+            .position(|c| c == card)
+            .ok_or(PuzzleErr::UnknownCard(*card))
     }
-}
 
-trait GetHandType {
-    fn hand_type(&self) -> HandType;
-}
+    fn validate(&self, hand: &Hand) -> Result<(), PuzzleErr<'static>> {
+        if hand.cards.len() != self.hand_size {
+            return Err(PuzzleErr::WrongHandSize(hand.cards.len(), self.hand_size));
+        }
+        for card in hand.cards.iter() {
+            self.strength(card)?;
+        }
+        Ok(())
+    }
 
-fn _card_count_to_hand_type<T: core::hash::Hash>(counts: &HashMap<T, u32>) -> HandType {
-    match counts.len() {
-        1 => HandType::FiveKind,
-        2 => match counts.values().max().unwrap() {
-            3 => HandType::FullHouse,
-            4 => HandType::FourKind,
-            _ => panic!("Logic error in card_count = 2."),
-        },
-        3 => match counts.values().max().unwrap() {
-            3 => HandType::ThreeKind,
-            2 => HandType::TwoPair,
-            _ => panic!("Logic error in card_count = 3."),
-        },
-        4 => HandType::OnePair,
-        5 => HandType::High,
-        _ => panic!("Too many cards in a hand."),
+    // Counts of the non-wild cards, most common first with ties going to the stronger
+    // card, and the number of wild cards.
+    fn card_counts(&self, hand: &Hand) -> (Vec<(char, usize)>, usize) {
+        let mut counts = HashMap::new();
+        let mut n_wild = 0;
+        for card in hand.cards.iter() {
+            if Some(*card) == self.wild {
+                n_wild += 1;
+            } else {
+                *counts.entry(*card).or_insert(0) += 1;
+            }
+        }
+        let mut counts = counts.into_iter().collect::<Vec<_>>();
+        counts.sort_by_key(|(c, n)| std::cmp::Reverse((*n, self.strength(c).unwrap_or(0))));
+        (counts, n_wild)
     }
-}
 
-impl GetHandType for Hand<Card1> {
-    fn hand_type(&self) -> HandType {
-        let card_count = count(&self.cards);
-        _card_count_to_hand_type(&card_count)
+    // Group sizes from largest to smallest, with any wild cards joining the largest group.
+    fn group_sizes(&self, hand: &Hand) -> Vec<usize> {
+        let (counts, n_wild) = self.card_counts(hand);
+        let mut sizes = counts.into_iter().map(|(_, n)| n).collect::<Vec<_>>();
+        match sizes.first_mut() {
+            Some(largest) => *largest += n_wild,
+            None => sizes.push(n_wild),
+        }
+        sizes
     }
-}
 
-impl GetHandType for Hand<Card2> {
-    fn hand_type(&self) -> HandType {
-        let mut card_count = count(&self.cards);
-        if card_count.len() == 1 {
-            return HandType::FiveKind;
+    // The card the hand's wild cards copy: its most common other card, or the strongest
+    // card if the hand is all wild. `None` if the hand has no wild cards.
+    pub fn substitution(&self, hand: &Hand) -> Option<char> {
+        let (counts, n_wild) = self.card_counts(hand);
+        if n_wild == 0 {
+            return None;
         }
-        if let Some(num_jokers) = card_count.clone().get(&Card2::J) {
-            card_count.remove(&Card2::J);
-            let max_count = card_count.values().max().unwrap();
-            let top_cards = card_count
+        match counts.first() {
+            Some((c, _)) => Some(*c),
+            None => self
+                .card_order
                 .iter()
-                .filter(|(_, v)| v == &max_count)
-                .map(|(k, _)| k)
-                .collect::<Vec<_>>();
-            let top_card = top_cards.first().unwrap();
-            card_count
-                .entry(**top_card)
-                .and_modify(|x| *x += num_jokers);
+                .rev()
+                .find(|c| Some(**c) != self.wild)
+                .or(self.wild.as_ref())
+                .copied(),
         }
-        _card_count_to_hand_type(&card_count)
     }
-}
 
-impl<C: Card> Ord for Hand<C>
-where
-    Hand<C>: GetHandType,
-{
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.cards == other.cards {
-            return Ordering::Equal;
+    // The type of a hand of any size, judged by its two largest groups of matching cards.
+    pub fn hand_type(&self, hand: &Hand) -> HandType {
+        let sizes = self.group_sizes(hand);
+        let second = sizes.get(1).copied().unwrap_or(0);
+        match (sizes[0], second) {
+            (5.., _) => HandType::FiveKind,
+            (4, _) => HandType::FourKind,
+            (3, 2..) => HandType::FullHouse,
+            (3, _) => HandType::ThreeKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::High,
         }
-        match self.hand_type().cmp(&other.hand_type()) {
-            Ordering::Equal => {
-                for (a, b) in zip(&self.cards, &other.cards) {
-                    if a != b {
-                        return a.cmp(b);
-                    }
-                }
-                panic!("Logic error in hand comparison.")
-            }
-            cmp_result => cmp_result,
+    }
+
+    fn tie_break_strengths(&self, hand: &Hand) -> Vec<usize> {
+        let mut strengths = hand
+            .cards
+            .iter()
+            .map(|c| self.strength(c).unwrap_or(0))
+            .collect::<Vec<_>>();
+        if self.tie_break == TieBreak::Sorted {
+            strengths.sort_by(|a, b| b.cmp(a));
         }
+        strengths
     }
-}
 
-impl<C: Card> PartialOrd for Hand<C>
-where
-    Hand<C>: GetHandType,
-{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    // Order two valid hands by type and then by the tie-break rule.
+    pub fn compare(&self, a: &Hand, b: &Hand) -> Ordering {
+        self.hand_type(a).cmp(&self.hand_type(b)).then_with(|| {
+            self.tie_break_strengths(a)
+                .cmp(&self.tie_break_strengths(b))
+        })
     }
-}
 
-impl<C: Card> PartialEq for Hand<C> {
-    fn eq(&self, other: &Self) -> bool {
-        self.cards == other.cards
+    // The hands from weakest to strongest, so a hand's rank is its position plus one.
+    pub fn rank<'a>(&self, hands: &[Hand]) -> Result<Vec<Hand>, PuzzleErr<'a>> {
+        for hand in hands.iter() {
+            self.validate(hand)?;
+        }
+        let mut ranked = hands.to_vec();
+        ranked.sort_by(|a, b| self.compare(a, b));
+        Ok(ranked)
     }
-}
 
-impl<C: Card> Eq for Hand<C> {}
+    // A report for every hand, from rank 1 (the weakest) upwards.
+    pub fn report<'a>(&self, hands: &[Hand]) -> Result<Vec<HandReport>, PuzzleErr<'a>> {
+        Ok(self
+            .rank(hands)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, hand)| HandReport {
+                hand_type: self.hand_type(&hand),
+                rank: i as u32 + 1,
+                winnings: (i as u32 + 1) * hand.bid,
+                substitution: self.substitution(&hand),
+                hand,
+            })
+            .collect())
+    }
+
+    pub fn total_winnings<'a>(&self, hands: &[Hand]) -> Result<u32, PuzzleErr<'a>> {
+        Ok(self.report(hands)?.iter().map(|r| r.winnings).sum())
+    }
+}
 
-fn _line_to_hand<C: Card>(line: &str) -> Result<Hand<C>, PuzzleErr>
-where
-    C: Clone + Copy + PartialEq + Eq + PartialOrd + Ord + core::hash::Hash,
-{
-    let split_str = line.split_whitespace().collect::<Vec<_>>();
-    let cards = split_str[0]
-        .chars()
-        .map(C::from_str)
-        .collect::<Result<Vec<_>, PuzzleErr>>()?;
-    let bid = split_str[1]
+fn _line_to_hand(line: &str) -> Result<Hand, PuzzleErr<'_>> {
+    let Some((cards, bid)) = line.trim().split_once(' ') else {
+        return Err(PuzzleErr::InputParsingError(line));
+    };
+    let bid = bid
+        .trim()
         .parse::<u32>()
         .or(Err(PuzzleErr::InputParsingError(line)))?;
-    Ok(Hand::new(cards, bid))
+    Ok(Hand {
+        cards: cards.chars().collect(),
+        bid,
+    })
 }
 
-fn parse_input<C: Card>(input: &str) -> Result<Vec<Hand<C>>, PuzzleErr> {
+pub fn parse_input(input: &str) -> Result<Vec<Hand>, PuzzleErr<'_>> {
     input.trim().lines().map(_line_to_hand).collect()
 }
 
-fn score_hands<C: Card>(cards: &mut [Hand<C>]) -> u32
-where
-    Hand<C>: GetHandType,
-{
-    cards.sort();
-    cards
-        .iter()
-        .enumerate()
-        .map(|(i, h)| (i as u32 + 1) * h.bid)
-        .sum()
+pub fn puzzle_1(input: &str) -> Result<u32, PuzzleErr<'_>> {
+    Ruleset::standard().total_winnings(&parse_input(input)?)
 }
 
-pub fn puzzle_1(input: &str) -> Result<u32, PuzzleErr> {
-    Ok(score_hands(&mut parse_input::<Card1>(input)?))
+pub fn puzzle_2(input: &str) -> Result<u32, PuzzleErr<'_>> {
+    Ruleset::jokers().total_winnings(&parse_input(input)?)
 }
 
-pub fn puzzle_2(input: &str) -> Result<u32, PuzzleErr> {
-    Ok(score_hands(&mut parse_input::<Card2>(input)?))
+// Print every hand's type, rank, and winnings under the part 1 or part 2 rules.
+pub fn print_report(data_dir: &str, jokers: bool) {
+    let data = load(data_dir, 7, None);
+    let rules = match jokers {
+        true => Ruleset::jokers(),
+        false => Ruleset::standard(),
+    };
+    let reports = match parse_input(&data).and_then(|hands| rules.report(&hands)) {
+        Ok(r) => r,
+        Err(e) => panic!("Failed to rank hands: {}", e),
+    };
+    println!(
+        "{:>5}  {:<5}  {:<10}  {:>5}  {:>10}",
+        "rank", "hand", "type", "bid", "winnings"
+    );
+    for report in reports.iter() {
+        println!("{}", report);
+    }
+    println!(
+        "Total winnings: {}",
+        reports.iter().map(|r| r.winnings).sum::<u32>()
+    );
 }
 
 pub fn main(data_dir: &str) {
@@ -1,4 +1,5 @@
 use crate::data::load;
+use crate::math_utils::{MathError, PolynomialSequence};
 use std::num::ParseIntError;
 use thiserror::Error;
 
@@ -6,9 +7,11 @@ use thiserror::Error;
 pub enum PuzzleErr {
     #[error("Integer parsing error.")]
     Disconnect(#[from] ParseIntError),
+    #[error("Extrapolation error: {}", .0)]
+    Extrapolation(#[from] MathError),
 }
 
-fn parse_input(data: &str) -> Result<Vec<Vec<i32>>, PuzzleErr> {
+fn parse_input(data: &str) -> Result<Vec<Vec<i64>>, PuzzleErr> {
     Ok(data
         .trim()
         .lines()
@@ -20,42 +23,18 @@ fn parse_input(data: &str) -> Result<Vec<Vec<i32>>, PuzzleErr> {
         .collect::<Result<Vec<_>, ParseIntError>>()?)
 }
 
-fn oasis_prediction(seq: &[i32]) -> i32 {
-    let mut diffs = Vec::from_iter([seq.to_owned()]);
-    loop {
-        let next_seq = diffs
-            .last()
-            .unwrap()
-            .windows(2)
-            .map(|a| a[1] - a[0])
-            .collect::<Vec<_>>();
-        diffs.push(next_seq.clone());
-        if next_seq.iter().all(|a| a == &0) {
-            break;
-        }
-    }
-
-    let mut preds = Vec::from_iter([0]);
-    for i in (1..diffs.len()).rev() {
-        preds.push(preds.last().unwrap() + diffs[i - 1].last().unwrap());
-    }
-    *preds.last().unwrap()
+pub fn puzzle_1(input: &str) -> Result<i64, PuzzleErr> {
+    parse_input(input)?
+        .iter()
+        .map(|s| Ok(PolynomialSequence::fit(s)?.predict_ahead(1)?))
+        .sum()
 }
 
-pub fn puzzle_1(input: &str) -> Result<i32, PuzzleErr> {
-    let seqs = parse_input(input)?;
-    Ok(seqs.iter().map(|s| oasis_prediction(s)).sum())
-}
-
-pub fn puzzle_2(input: &str) -> Result<i32, PuzzleErr> {
-    let seqs = parse_input(input)?
-        .iter_mut()
-        .map(|s| {
-            s.reverse();
-            s.clone()
-        })
-        .collect::<Vec<_>>();
-    Ok(seqs.iter().map(|s| oasis_prediction(s)).sum())
+pub fn puzzle_2(input: &str) -> Result<i64, PuzzleErr> {
+    parse_input(input)?
+        .iter()
+        .map(|s| Ok(PolynomialSequence::fit(s)?.predict_behind(1)?))
+        .sum()
 }
 
 pub fn main(data_dir: &str) {
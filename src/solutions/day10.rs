@@ -1,13 +1,22 @@
 use crate::data::load;
-use petgraph::algo::{self, DfsSpace};
-use petgraph::{graph::NodeIndex, graph::UnGraph};
 use std::collections::{HashMap, HashSet};
+use std::fs;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleErr {
     #[error("Unknown pipe character: {}.", .0)]
     UnknownPipeChar(String),
+    #[error("No start tile in the maze.")]
+    MissingStart,
+    #[error("More than one start tile in the maze.")]
+    MultipleStarts,
+    #[error("Cannot infer the pipe under the start tile at {:?}.", .0)]
+    AmbiguousStart(Coord),
+    #[error("The main loop is broken at {:?}.", .0)]
+    BrokenLoop(Coord),
+    #[error("Error writing rendered maze: {}.", .0)]
+    WriteError(String),
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -39,24 +48,49 @@ impl TryFrom<&char> for Pipe {
     }
 }
 
+const PIPES: [Pipe; 6] = [Pipe::V, Pipe::H, Pipe::NE, Pipe::NW, Pipe::SW, Pipe::SE];
+
 impl Pipe {
-    fn is_turn(&self) -> bool {
-        matches!(self, Pipe::NE | Pipe::NW | Pipe::SW | Pipe::SE)
+    // The (row, column) offsets of the two tiles a pipe connects.
+    fn directions(&self) -> Vec<(i32, i32)> {
+        match self {
+            Pipe::V => Vec::from_iter([(-1, 0), (1, 0)]),
+            Pipe::H => Vec::from_iter([(0, -1), (0, 1)]),
+            Pipe::NE => Vec::from_iter([(-1, 0), (0, 1)]),
+            Pipe::NW => Vec::from_iter([(-1, 0), (0, -1)]),
+            Pipe::SW => Vec::from_iter([(1, 0), (0, -1)]),
+            Pipe::SE => Vec::from_iter([(1, 0), (0, 1)]),
+            Pipe::G | Pipe::S => Vec::new(),
+        }
+    }
+
+    fn connects(&self, dir: &(i32, i32)) -> bool {
+        self.directions().contains(dir)
     }
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord {
-    r: i32,
-    c: i32,
+pub struct Coord {
+    pub r: i32,
+    pub c: i32,
+}
+
+impl Coord {
+    fn step(&self, dir: &(i32, i32)) -> Self {
+        Self {
+            r: self.r + dir.0,
+            c: self.c + dir.1,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 struct Maze {
+    // The start tile is replaced by the pipe inferred from its neighbors.
     map: HashMap<Coord, Pipe>,
-    graph: UnGraph<Coord, ()>,
-    start_i: NodeIndex,
-    node_indices: HashMap<Coord, NodeIndex>,
+    start: Coord,
+    // Tiles of the main loop in order, beginning with the start tile.
+    main_loop: Vec<Coord>,
 }
 
 fn _build_map(input: &str) -> Result<HashMap<Coord, Pipe>, PuzzleErr> {
@@ -75,155 +109,70 @@ fn _build_map(input: &str) -> Result<HashMap<Coord, Pipe>, PuzzleErr> {
     Ok(map)
 }
 
-fn _add_neighbors(
-    graph: &mut UnGraph<Coord, ()>,
-    nodes: &HashMap<Coord, NodeIndex>,
-    node_idx: &NodeIndex,
-    coord: &Coord,
-    directions: &Vec<(i32, i32)>,
-) {
-    for (dr, dc) in directions {
-        let neighbor = Coord {
-            r: coord.r + dr,
-            c: coord.c + dc,
-        };
-        if let Some(neighbor_i) = nodes.get(&neighbor) {
-            graph.add_edge(*node_idx, *neighbor_i, ());
-        }
+fn _find_start(map: &HashMap<Coord, Pipe>) -> Result<Coord, PuzzleErr> {
+    let starts = map
+        .iter()
+        .filter(|(_, p)| p == &&Pipe::S)
+        .map(|(c, _)| *c)
+        .collect::<Vec<_>>();
+    match starts.len() {
+        0 => Err(PuzzleErr::MissingStart),
+        1 => Ok(starts[0]),
+        _ => Err(PuzzleErr::MultipleStarts),
     }
 }
 
-fn _map_to_graph(
-    map: &HashMap<Coord, Pipe>,
-) -> (UnGraph<Coord, ()>, HashMap<Coord, NodeIndex>, NodeIndex) {
-    let mut nodes = HashMap::new();
-    let mut graph = UnGraph::new_undirected();
-    let mut start_i: Option<NodeIndex> = None;
-    for (c, p) in map.iter() {
-        match p {
-            Pipe::G => (),
-            Pipe::S => {
-                start_i = Some(graph.add_node(*c));
-                nodes.insert(*c, start_i.unwrap());
-            }
-            _ => {
-                nodes.insert(*c, graph.add_node(*c));
-            }
-        };
-    }
-
-    for (coord, p) in map.iter() {
-        if let Some(node_idx) = nodes.get(coord) {
-            let directions = match p {
-                Pipe::V => Vec::from_iter([(-1, 0), (1, 0)]),
-                Pipe::H => Vec::from_iter([(0, -1), (0, 1)]),
-                Pipe::NE => Vec::from_iter([(-1, 0), (0, 1)]),
-                Pipe::NW => Vec::from_iter([(-1, 0), (0, -1)]),
-                Pipe::SW => Vec::from_iter([(1, 0), (0, -1)]),
-                Pipe::SE => Vec::from_iter([(1, 0), (0, 1)]),
-                Pipe::S => Vec::from_iter([(-1, 0), (1, 0), (0, -1), (0, 1)]),
-                Pipe::G => Vec::new(),
-            };
-            _add_neighbors(&mut graph, &nodes, node_idx, coord, &directions);
-        }
-    }
+// The start pipe must connect to exactly the two neighbors that connect back to it.
+fn _infer_start_pipe(map: &HashMap<Coord, Pipe>, start: &Coord) -> Result<Pipe, PuzzleErr> {
+    let connected = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+        .into_iter()
+        .filter(|d| {
+            map.get(&start.step(d))
+                .is_some_and(|p| p.connects(&(-d.0, -d.1)))
+        })
+        .collect::<HashSet<_>>();
+    PIPES
+        .into_iter()
+        .find(|p| p.directions().into_iter().collect::<HashSet<_>>() == connected)
+        .ok_or(PuzzleErr::AmbiguousStart(*start))
+}
 
-    // Only keep edges between nodes with two edges at this point.
-    let node_indices = graph.node_indices().collect::<Vec<_>>();
-    let mut keep_edges = HashSet::new();
-    for (i, n1) in node_indices.iter().enumerate() {
-        for n2 in node_indices[(i + 1)..].iter() {
-            let edges = graph.edges_connecting(*n1, *n2).collect::<Vec<_>>();
-            if edges.len() == 2 {
-                keep_edges.insert(graph.find_edge(*n1, *n2).unwrap());
-            }
+fn _trace_loop(map: &HashMap<Coord, Pipe>, start: &Coord) -> Result<Vec<Coord>, PuzzleErr> {
+    let mut main_loop = Vec::from_iter([*start]);
+    let mut dir = map[start].directions()[0];
+    let mut coord = start.step(&dir);
+    while &coord != start {
+        let back = (-dir.0, -dir.1);
+        let pipe = map.get(&coord).ok_or(PuzzleErr::BrokenLoop(coord))?;
+        if !pipe.connects(&back) {
+            return Err(PuzzleErr::BrokenLoop(coord));
         }
+        main_loop.push(coord);
+        dir = pipe.directions().into_iter().find(|d| d != &back).unwrap();
+        coord = coord.step(&dir);
     }
-    graph.retain_edges(|_, e| keep_edges.contains(&e));
-
-    // Only retain nodes connected to S.
-    let mut dfs_space = DfsSpace::default();
-    let keep_nodes = graph
-        .node_indices()
-        .filter(|n| algo::has_path_connecting(&graph, start_i.unwrap(), *n, Some(&mut dfs_space)))
-        .collect::<HashSet<_>>();
-    graph.retain_nodes(|_, n| keep_nodes.contains(&n));
-
-    (graph, nodes, start_i.unwrap())
+    Ok(main_loop)
 }
 
 fn parse_input(data: &str) -> Result<Maze, PuzzleErr> {
-    let map = _build_map(data)?;
-    let (graph, node_indices, start_i) = _map_to_graph(&map);
+    let mut map = _build_map(data)?;
+    let start = _find_start(&map)?;
+    let start_pipe = _infer_start_pipe(&map, &start)?;
+    map.insert(start, start_pipe);
+    let main_loop = _trace_loop(&map, &start)?;
     Ok(Maze {
         map,
-        graph,
-        start_i,
-        node_indices,
+        start,
+        main_loop,
     })
 }
 
-pub fn puzzle_1(input: &str) -> Result<i32, PuzzleErr> {
-    let maze = parse_input(input)?;
-    Ok(*algo::dijkstra(&maze.graph, maze.start_i, None, |_| 1)
-        .values()
-        .max()
-        .unwrap())
-}
-
-fn _counter_turning_pipes(a: &Pipe, b: &Pipe) -> bool {
-    matches!(
-        (a, b),
-        (Pipe::NW, Pipe::SE) | (Pipe::SE, Pipe::NW) | (Pipe::SW, Pipe::NE) | (Pipe::NE, Pipe::SW)
-    )
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Decision {
-    Break,
-    Continue,
-    Insert,
-    UpdatePrevTurn,
+pub fn main_loop(input: &str) -> Result<Vec<Coord>, PuzzleErr> {
+    Ok(parse_input(input)?.main_loop)
 }
 
-fn collection_decision(
-    coord: &Coord,
-    pipe: &Pipe,
-    graph_nodes: &HashMap<Coord, NodeIndex>,
-    prev_turn: &Option<Pipe>,
-    straight_breaking_pipe: &Pipe,
-) -> Decision {
-    if !graph_nodes.contains_key(coord) {
-        log::debug!("Insert coord.");
-        return Decision::Insert;
-    }
-
-    if pipe == straight_breaking_pipe {
-        log::debug!("Break pipe.");
-        return Decision::Break;
-    }
-
-    if !pipe.is_turn() {
-        log::debug!("Straight pipe, just continue.");
-        return Decision::Continue;
-    }
-
-    log::debug!("Previous, current pipes:  {:?}, {:?}", prev_turn, pipe);
-    match (prev_turn, pipe) {
-        (None, _) => {
-            log::debug!("Update previous turning pipe.");
-            Decision::UpdatePrevTurn
-        }
-        (Some(a), b) => {
-            if _counter_turning_pipes(a, b) {
-                log::debug!("Counter turning pipes, break.");
-                Decision::Break
-            } else {
-                log::debug!("Not-counter turning pipes, continue.");
-                Decision::UpdatePrevTurn
-            }
-        }
-    }
+pub fn puzzle_1(input: &str) -> Result<i32, PuzzleErr> {
+    Ok(main_loop(input)?.len() as i32 / 2)
 }
 
 fn get_maxes<T>(map: &HashMap<Coord, T>) -> (i32, i32) {
@@ -232,183 +181,183 @@ fn get_maxes<T>(map: &HashMap<Coord, T>) -> (i32, i32) {
     (max_r, max_c)
 }
 
-fn collect_nodes_visible_from_outside(
-    map: &HashMap<Coord, Pipe>,
-    graph_nodes: &HashMap<Coord, NodeIndex>,
-) -> HashSet<Coord> {
-    let mut outsides = HashSet::new();
-    let (max_r, max_c) = get_maxes(map);
+// Scan each row, flipping between outside and inside every time the main loop is
+// crossed. Only pipes connecting north count as a crossing, so a run along the loop
+// such as "L--7" crosses it once and "L--J" not at all.
+fn find_enclosed_tiles(maze: &Maze) -> HashSet<Coord> {
+    let loop_tiles = maze.main_loop.iter().collect::<HashSet<_>>();
+    let (max_r, max_c) = get_maxes(&maze.map);
+    let mut enclosed = HashSet::new();
     for r in 0..=max_r {
-        let mut prev_turn: Option<Pipe> = None;
+        let mut inside = false;
         for c in 0..=max_c {
             let coord = Coord { r, c };
-            let pipe = map.get(&coord).unwrap();
-            log::debug!("Checking coord {:?} with character {:?}", coord, pipe);
-            match collection_decision(&coord, pipe, graph_nodes, &prev_turn, &Pipe::V) {
-                Decision::Continue => {}
-                Decision::Break => break,
-                Decision::Insert => {
-                    outsides.insert(coord);
-                }
-                Decision::UpdatePrevTurn => {
-                    prev_turn = Some(*pipe);
-                }
-            }
-        }
-
-        prev_turn = None;
-        for c in (0..=max_c).rev() {
-            let coord = Coord { r, c };
-            let pipe = map.get(&coord).unwrap();
-            match collection_decision(&coord, pipe, graph_nodes, &prev_turn, &Pipe::V) {
-                Decision::Continue => {}
-                Decision::Break => break,
-                Decision::Insert => {
-                    outsides.insert(coord);
-                }
-                Decision::UpdatePrevTurn => {
-                    prev_turn = Some(*pipe);
+            if loop_tiles.contains(&coord) {
+                if maze.map[&coord].connects(&(-1, 0)) {
+                    inside = !inside;
                 }
+            } else if inside {
+                enclosed.insert(coord);
             }
         }
     }
+    enclosed
+}
 
-    for c in 0..=max_c {
-        let mut prev_turn: Option<Pipe> = None;
-        for r in 0..=max_r {
-            let coord = Coord { r, c };
-            let pipe = map.get(&coord).unwrap();
-            match collection_decision(&coord, pipe, graph_nodes, &prev_turn, &Pipe::H) {
-                Decision::Continue => {}
-                Decision::Break => break,
-                Decision::Insert => {
-                    outsides.insert(coord);
-                }
-                Decision::UpdatePrevTurn => {
-                    prev_turn = Some(*pipe);
-                }
-            }
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Text,
+    Ansi,
+    Svg,
+}
 
-        prev_turn = None;
-        for r in (0..=max_r).rev() {
-            let coord = Coord { r, c };
-            let pipe = map.get(&coord).unwrap();
-            match collection_decision(&coord, pipe, graph_nodes, &prev_turn, &Pipe::H) {
-                Decision::Continue => {}
-                Decision::Break => break,
-                Decision::Insert => {
-                    outsides.insert(coord);
-                }
-                Decision::UpdatePrevTurn => {
-                    prev_turn = Some(*pipe);
-                }
-            }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Region {
+    Start,
+    Loop,
+    Inside,
+    Outside,
+}
+
+impl Pipe {
+    fn box_char(&self) -> char {
+        match self {
+            Pipe::V => '│',
+            Pipe::H => '─',
+            Pipe::NE => '└',
+            Pipe::NW => '┘',
+            Pipe::SW => '┐',
+            Pipe::SE => '┌',
+            Pipe::G => '.',
+            Pipe::S => 'S',
         }
     }
-
-    outsides
 }
 
-fn make_opposite_graphs(
-    input_map: &HashMap<Coord, Pipe>,
-    graph1_nodes: &HashMap<Coord, NodeIndex>,
-) -> (UnGraph<Coord, ()>, HashMap<Coord, NodeIndex>) {
-    let mut outs_graph = UnGraph::new_undirected();
-    let outs_nodes = input_map
-        .iter()
-        .filter(|(c, _)| !graph1_nodes.contains_key(c))
-        .map(|(c, _)| (*c, outs_graph.add_node(*c)))
-        .collect::<HashMap<Coord, NodeIndex>>();
+fn classify_tiles(maze: &Maze, enclosed: &HashSet<Coord>) -> HashMap<Coord, Region> {
+    let loop_tiles = maze.main_loop.iter().collect::<HashSet<_>>();
+    maze.map
+        .keys()
+        .map(|coord| {
+            let region = if coord == &maze.start {
+                Region::Start
+            } else if loop_tiles.contains(coord) {
+                Region::Loop
+            } else if enclosed.contains(coord) {
+                Region::Inside
+            } else {
+                Region::Outside
+            };
+            (*coord, region)
+        })
+        .collect()
+}
 
-    let (max_r, max_c) = get_maxes(&outs_nodes);
+// Loop pipes are drawn with box-drawing characters and all other tiles as "I" (inside)
+// or "O" (outside), matching the puzzle text.
+fn render_text(maze: &Maze, enclosed: &HashSet<Coord>, ansi: bool) -> String {
+    let regions = classify_tiles(maze, enclosed);
+    let (max_r, max_c) = get_maxes(&maze.map);
+    (0..=max_r)
+        .map(|r| {
+            (0..=max_c)
+                .map(|c| {
+                    let coord = Coord { r, c };
+                    // Rows may be shorter than the widest row; their missing tiles are blank.
+                    let (symbol, color) = match regions.get(&coord) {
+                        Some(Region::Start) => (maze.map[&coord].box_char(), "\x1b[1;31m"),
+                        Some(Region::Loop) => (maze.map[&coord].box_char(), "\x1b[1;33m"),
+                        Some(Region::Inside) => ('I', "\x1b[32m"),
+                        Some(Region::Outside) => ('O', "\x1b[2;34m"),
+                        None => return ' '.to_string(),
+                    };
+                    if ansi {
+                        format!("{}{}\x1b[0m", color, symbol)
+                    } else {
+                        symbol.to_string()
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-    for (coord, idx) in outs_nodes.iter() {
-        for (dr, dc) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            let neighbor_coord = Coord {
-                r: coord.r + dr,
-                c: coord.c + dc,
-            };
-            if (neighbor_coord.r < 0)
-                | (neighbor_coord.c < 0)
-                | (neighbor_coord.r > max_r)
-                | (neighbor_coord.c > max_c)
-            {
-                continue;
-            } else if !graph1_nodes.contains_key(&neighbor_coord) {
-                log::debug!("Making edge: {:?}  -->  {:?}", coord, neighbor_coord);
-                let neighbor_idx = outs_nodes.get(&neighbor_coord).unwrap();
-                if !outs_graph.contains_edge(*idx, *neighbor_idx) {
-                    outs_graph.add_edge(*idx, *neighbor_idx, ());
-                }
-            }
-        }
+const SVG_TILE: i32 = 10;
+
+fn render_svg(maze: &Maze, enclosed: &HashSet<Coord>) -> String {
+    let (max_r, max_c) = get_maxes(&maze.map);
+    let (height, width) = ((max_r + 1) * SVG_TILE, (max_c + 1) * SVG_TILE);
+    let center = |c: &Coord| (c.c * SVG_TILE + SVG_TILE / 2, c.r * SVG_TILE + SVG_TILE / 2);
+
+    let mut lines = Vec::from_iter([
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        ),
+        format!(
+            r##"<rect width="{}" height="{}" fill="#dde3ea"/>"##,
+            width, height
+        ),
+    ]);
+    let mut inside = enclosed.iter().collect::<Vec<_>>();
+    inside.sort_by_key(|c| (c.r, c.c));
+    for coord in inside {
+        lines.push(format!(
+            r##"<rect x="{}" y="{}" width="{t}" height="{t}" fill="#6cc070"/>"##,
+            coord.c * SVG_TILE,
+            coord.r * SVG_TILE,
+            t = SVG_TILE
+        ));
     }
-    (outs_graph, outs_nodes)
+    let points = maze
+        .main_loop
+        .iter()
+        .map(|c| {
+            let (x, y) = center(c);
+            format!("{},{}", x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    lines.push(format!(
+        r##"<polygon points="{}" fill="none" stroke="#d9822b" stroke-width="3" stroke-linejoin="round"/>"##,
+        points
+    ));
+    let (x, y) = center(&maze.start);
+    lines.push(format!(
+        r##"<circle cx="{}" cy="{}" r="{}" fill="#c23030"/>"##,
+        x,
+        y,
+        SVG_TILE / 3
+    ));
+    lines.push("</svg>".to_string());
+    lines.join("\n")
 }
 
-fn log_map(map: &HashMap<Coord, Pipe>, nodes: &HashMap<Coord, NodeIndex>) {
-    let (max_r, max_c) = get_maxes(map);
-    let mut row_strs = Vec::new();
-    for r in 0..=max_r {
-        let mut row = Vec::new();
-        for c in 0..=max_c {
-            let coord = Coord { r, c };
-            if nodes.contains_key(&coord) {
-                row.push(".")
-            } else {
-                row.push(" ")
-            }
-        }
-        row_strs.push(row.join(" ").to_string())
-    }
-    let graph_str = row_strs.join("\n");
-    log::info!("MAZE:\n{}", graph_str);
+pub fn render(input: &str, format: RenderFormat) -> Result<String, PuzzleErr> {
+    let maze = parse_input(input)?;
+    let enclosed = find_enclosed_tiles(&maze);
+    Ok(match format {
+        RenderFormat::Text => render_text(&maze, &enclosed, false),
+        RenderFormat::Ansi => render_text(&maze, &enclosed, true),
+        RenderFormat::Svg => render_svg(&maze, &enclosed),
+    })
 }
 
-// !NOTE: Currently fails the fourth example.
-// !Seems like there is a bug in the building of the starting map.
-// (Answer with current algorithm, 404, is too low.)
+pub fn write_render(input: &str, format: RenderFormat, path: &str) -> Result<(), PuzzleErr> {
+    fs::write(path, render(input, format)?).map_err(|e| PuzzleErr::WriteError(e.to_string()))
+}
 
-pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
-    // 1. Create graph from puzzle 1.
+pub fn enclosed_tiles(input: &str) -> Result<HashSet<Coord>, PuzzleErr> {
     let maze = parse_input(input)?;
-    log_map(&maze.map, &maze.node_indices);
-
-    // 2. Create a graph all other nodes not in graph 1.
-    let (mut outside_graph, node_idx) = make_opposite_graphs(&maze.map, &maze.node_indices);
-    // use petgraph::dot::{Config, Dot};
-    // log::debug!(
-    //     "\n{:?}",
-    //     Dot::with_config(&outside_graph, &[Config::EdgeNoLabel])
-    // );
-
-    // 3. Collect nodes directly "visible" from the outside.
-    let outsides = collect_nodes_visible_from_outside(&maze.map, &maze.node_indices);
-    log::debug!("Outsides:");
-    for o in outsides.iter() {
-        log::debug!(" {:?}", o);
-    }
-
-    // 4. Remove nodes connected to the nodes "visible" from the outside.
-    let mut outsides_idx = HashSet::new();
-    for out in outsides.iter() {
-        outsides_idx.insert(node_idx.get(out).unwrap());
-    }
-    let mut dfs_space = DfsSpace::default();
-    for n_i in node_idx.values() {
-        for out in outsides.iter() {
-            let out_i = *node_idx.get(out).unwrap();
-            if algo::has_path_connecting(&outside_graph, *n_i, out_i, Some(&mut dfs_space)) {
-                outsides_idx.insert(n_i);
-                break;
-            }
-        }
-    }
-    outside_graph.retain_nodes(|_, n| !outsides_idx.contains(&n));
+    let enclosed = find_enclosed_tiles(&maze);
+    log::debug!("MAZE:\n{}", render_text(&maze, &enclosed, true));
+    Ok(enclosed)
+}
 
-    // Solution is the number of remaining nodes.
-    Ok(outside_graph.node_count())
+pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
+    Ok(enclosed_tiles(input)?.len())
 }
 
 pub fn main(data_dir: &str) {
@@ -424,11 +373,10 @@ pub fn main(data_dir: &str) {
     assert_eq!(answer_1, Ok(6867));
 
     // Puzzle 2.
-    // let answer_2 = puzzle_2(&data);
-    // match answer_2 {
-    //     Ok(x) => println!(" Puzzle 2: {}", x),
-    //     Err(e) => panic!("No solution to puzzle 2: {}", e),
-    // }
-    // 404 (too low)
-    // assert_eq!(answer_2, Ok(933))
+    let answer_2 = puzzle_2(&data);
+    match answer_2 {
+        Ok(x) => println!(" Puzzle 2: {}", x),
+        Err(e) => panic!("No solution to puzzle 2: {}", e),
+    }
+    assert_eq!(answer_2, Ok(595))
 }
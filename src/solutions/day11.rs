@@ -1,106 +1,127 @@
 use crate::data::load;
-use ndarray::prelude::*;
-use std::iter::zip;
+use std::collections::{BTreeSet, BinaryHeap};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Coord {
-    r: usize,
-    c: usize,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub r: u128,
+    pub c: u128,
 }
 
 impl Coord {
-    fn t(&self) -> Self {
-        Self {
-            r: self.c,
-            c: self.r,
-        }
-    }
-
-    fn dist(&self, other: &Self) -> usize {
+    fn dist(&self, other: &Self) -> u128 {
         self.r.abs_diff(other.r) + self.c.abs_diff(other.c)
     }
 }
 
-#[derive(Debug, Clone)]
-struct CosmicMap {
-    arr: Array2<u8>,
-    galaxies: Vec<Coord>,
-}
-
-impl CosmicMap {
-    fn t(&self) -> Self {
-        Self {
-            arr: self.arr.t().to_owned(),
-            galaxies: self.galaxies.iter().map(|c| c.t()).collect(),
-        }
-    }
-}
-
-fn parse_map(input: &str) -> CosmicMap {
-    let lines = input.trim().lines().map(|l| l.trim()).collect::<Vec<_>>();
-    let width = lines.first().unwrap().len();
-    let height = lines.len();
-    let mut arr = Array2::zeros((height, width));
+// Galaxies in reading order.
+fn parse_galaxies(input: &str) -> Vec<Coord> {
     let mut galaxies = Vec::new();
-    for (i, line) in lines.iter().enumerate() {
-        for (j, c) in line.chars().enumerate() {
+    for (i, line) in input.trim().lines().enumerate() {
+        for (j, c) in line.trim().chars().enumerate() {
             if c == '#' {
-                arr.slice_mut(s![i, j]).fill(1);
-                galaxies.push(Coord { r: i, c: j });
+                galaxies.push(Coord {
+                    r: i as u128,
+                    c: j as u128,
+                });
             }
         }
     }
-    CosmicMap { arr, galaxies }
+    galaxies
 }
 
-fn calculate_dists(coords: &[Coord]) -> Vec<usize> {
-    let mut dists = Vec::new();
-    for (i, a) in coords.iter().enumerate() {
-        for b in coords[(i + 1)..].iter() {
-            dists.push(a.dist(b))
-        }
+// Each empty row or column is replaced by `factor` empty rows or columns, so only
+// indices up to the last galaxy matter and the map's size is never needed.
+fn expand_axis(xs: &[u128], factor: u128) -> Vec<u128> {
+    let occupied = xs.iter().copied().collect::<BTreeSet<_>>();
+    let mut n_empty_before = Vec::with_capacity(occupied.len());
+    let mut prev = None;
+    let mut n_empty = 0;
+    for x in occupied.iter() {
+        n_empty += match prev {
+            Some(p) => x - p - 1,
+            None => *x,
+        };
+        n_empty_before.push((*x, n_empty));
+        prev = Some(*x);
+    }
+    xs.iter()
+        .map(|x| {
+            let i = n_empty_before.partition_point(|(y, _)| y < x);
+            x + n_empty_before[i].1 * (factor - 1)
+        })
+        .collect()
+}
+
+// Galaxy positions after empty rows grow by `row_factor` and empty columns by
+// `col_factor`. A factor of 1 leaves the axis as it is.
+pub fn expanded_galaxies(input: &str, row_factor: u128, col_factor: u128) -> Vec<Coord> {
+    let galaxies = parse_galaxies(input);
+    let rs = galaxies.iter().map(|g| g.r).collect::<Vec<_>>();
+    let cs = galaxies.iter().map(|g| g.c).collect::<Vec<_>>();
+    expand_axis(&rs, row_factor.max(1))
+        .into_iter()
+        .zip(expand_axis(&cs, col_factor.max(1)))
+        .map(|(r, c)| Coord { r, c })
+        .collect()
+}
+
+// Sum of |a - b| over all pairs: after sorting, the `i`-th value is larger than the
+// `i` values before it.
+fn axis_distance_sum(mut xs: Vec<u128>) -> u128 {
+    xs.sort();
+    let mut prefix = 0;
+    let mut total = 0;
+    for (i, x) in xs.iter().enumerate() {
+        total += x * i as u128 - prefix;
+        prefix += x;
     }
-    dists
+    total
 }
 
-fn expand_galaxies(map: &CosmicMap, ex_rate: usize) -> Vec<Coord> {
-    let x = match ex_rate {
-        0..=1 => ex_rate,
-        _ => ex_rate - 1,
-    };
-    let coords = map.galaxies.clone();
-    let mut add_rs = (0..coords.len()).map(|_| 0).collect::<Vec<_>>();
+pub fn sum_of_distances(input: &str, row_factor: u128, col_factor: u128) -> u128 {
+    let galaxies = expanded_galaxies(input, row_factor, col_factor);
+    axis_distance_sum(galaxies.iter().map(|g| g.r).collect())
+        + axis_distance_sum(galaxies.iter().map(|g| g.c).collect())
+}
 
-    for i in 0..map.arr.nrows() {
-        if map.arr.row(i).sum() == 0 {
-            add_rs = zip(&coords, add_rs)
-                .map(|(coord, add_r)| if coord.r > i { add_r + x } else { add_r })
-                .collect();
+// The `k` closest pairs of galaxies as `(distance, i, j)` with galaxy indices in reading
+// order, sorted by distance and then by index.
+pub fn nearest_pairs(
+    input: &str,
+    row_factor: u128,
+    col_factor: u128,
+    k: usize,
+) -> Vec<(u128, usize, usize)> {
+    let galaxies = expanded_galaxies(input, row_factor, col_factor);
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for (i, a) in galaxies.iter().enumerate() {
+        for (j, b) in galaxies.iter().enumerate().skip(i + 1) {
+            heap.push((a.dist(b), i, j));
+            if heap.len() > k {
+                heap.pop();
+            }
         }
     }
-
-    zip(coords, add_rs)
-        .map(|(coord, add_r)| Coord {
-            r: coord.r + add_r,
-            c: coord.c,
-        })
-        .collect()
+    heap.into_sorted_vec()
 }
 
-fn _solve(input: &str, expansion_size: usize) -> usize {
-    let mut map = parse_map(input);
-    map.galaxies = expand_galaxies(&map, expansion_size);
-    map = map.t();
-    map.galaxies = expand_galaxies(&map, expansion_size);
-    calculate_dists(&map.galaxies).iter().sum()
+// An expansion rate of 0 leaves the map as it is; otherwise each empty row or column
+// becomes `expansion_size` of them, with a rate of 1 treated as doubling.
+fn expansion_factor(expansion_size: usize) -> u128 {
+    match expansion_size {
+        0 => 1,
+        1 => 2,
+        x => x as u128,
+    }
 }
 
-pub fn puzzle_1(input: &str) -> usize {
-    _solve(input, 1)
+pub fn puzzle_1(input: &str) -> u128 {
+    sum_of_distances(input, 2, 2)
 }
 
-pub fn puzzle_2(input: &str, expansion_size: usize) -> usize {
-    _solve(input, expansion_size)
+pub fn puzzle_2(input: &str, expansion_size: usize) -> u128 {
+    let factor = expansion_factor(expansion_size);
+    sum_of_distances(input, factor, factor)
 }
 
 pub fn main(data_dir: &str) {
@@ -114,6 +135,6 @@ pub fn main(data_dir: &str) {
 
     // Puzzle 2.
     let answer_2 = puzzle_2(&data, 1000000);
-    println!(" Puzzle 1: {}", answer_2);
+    println!(" Puzzle 2: {}", answer_2);
     assert_eq!(answer_2, 569052586852);
 }
@@ -1,5 +1,4 @@
 use crate::data::load;
-use cached::proc_macro::cached;
 use itertools::Itertools;
 use std::{fmt::Display, num::ParseIntError};
 use thiserror::Error;
@@ -13,7 +12,7 @@ pub enum PuzzleErr {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Condition {
+pub enum Condition {
     Operational,
     Damaged,
     Unknown,
@@ -41,10 +40,10 @@ impl Display for Condition {
     }
 }
 
-#[derive(Debug, Clone)]
-struct Row {
-    conditions: Vec<Condition>,
-    groups: Vec<usize>,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub conditions: Vec<Condition>,
+    pub groups: Vec<usize>,
 }
 
 impl TryFrom<&str> for Row {
@@ -79,114 +78,162 @@ impl Display for Row {
     }
 }
 
-fn _split_condition_vec(conditions: &[Condition]) -> Vec<&[Condition]> {
-    conditions
-        .split(|x| x == &Condition::Operational)
-        .filter(|cs| !cs.is_empty())
-        .collect::<Vec<_>>()
-}
+impl Row {
+    // The row repeated `n_reps` times, joined by unknown springs.
+    pub fn unfold(&self, n_reps: usize) -> Self {
+        let mut conditions = (0..n_reps)
+            .map(|_| {
+                let mut c = self.conditions.clone();
+                c.push(Condition::Unknown);
+                c
+            })
+            .concat();
+        let _ = conditions.pop();
+        Self {
+            conditions,
+            groups: self.groups.repeat(n_reps),
+        }
+    }
 
-fn parse_input(input: &str) -> Result<Vec<Row>, PuzzleErr> {
-    input
-        .trim()
-        .lines()
-        .map(Row::try_from)
-        .collect::<Result<Vec<_>, PuzzleErr>>()
-}
+    // Whether the group of `len` damaged springs can start at index `i`: no operational
+    // springs within it and no damaged spring immediately after it.
+    fn group_fits(&self, i: usize, len: usize, n_operational_before: &[usize]) -> bool {
+        let end = i + len;
+        (end <= self.conditions.len())
+            && (n_operational_before[end] == n_operational_before[i])
+            && (self.conditions.get(end) != Some(&Condition::Damaged))
+    }
 
-fn add_operational(record: Vec<Condition>, groups: Vec<usize>) -> usize {
-    count_solutions(record[1..].to_vec(), groups)
-}
+    // `table[i][j]` is the number of arrangements of `conditions[i..]` into `groups[j..]`.
+    fn arrangement_table(&self) -> Vec<Vec<u128>> {
+        let (n, m) = (self.conditions.len(), self.groups.len());
+        let mut n_operational_before = vec![0; n + 1];
+        for (i, c) in self.conditions.iter().enumerate() {
+            n_operational_before[i + 1] =
+                n_operational_before[i] + (c == &Condition::Operational) as usize;
+        }
 
-fn add_damaged(record: Vec<Condition>, groups: Vec<usize>, next_group: usize) -> usize {
-    // This current group must be all "#" or "?" (no "." allowed).
-    if record.len() < next_group {
-        return 0;
-    }
-    if record[..next_group]
-        .iter()
-        .any(|c| c == &Condition::Operational)
-    {
-        return 0;
+        // Extra row so that a group ending at the last spring can skip its separator.
+        let mut table = vec![vec![0; m + 1]; n + 2];
+        table[n][m] = 1;
+        table[n + 1][m] = 1;
+        for i in (0..n).rev() {
+            for j in 0..=m {
+                let cond = self.conditions[i];
+                let mut count = 0;
+                if cond != Condition::Damaged {
+                    count += table[i + 1][j];
+                }
+                if (cond != Condition::Operational)
+                    && (j < m)
+                    && self.group_fits(i, self.groups[j], &n_operational_before)
+                {
+                    count += table[i + self.groups[j] + 1][j + 1];
+                }
+                table[i][j] = count;
+            }
+        }
+        table
     }
 
-    // If the remaining record is the length of the group, then successful if there are
-    // no more remaining groups.
-    if record.len() == next_group {
-        if groups.len() == 1 {
-            return 1;
-        } else {
-            return 0;
-        }
+    pub fn count_arrangements(&self) -> u128 {
+        self.arrangement_table()[0][0]
     }
 
-    // The next character after the group cannot be "#".
-    match record[next_group] {
-        Condition::Damaged => 0,
-        _ => count_solutions(record[(next_group + 1)..].to_vec(), groups[1..].to_vec()),
+    // The `k`-th arrangement (from 0) with every unknown spring resolved, ordering
+    // arrangements by placing operational springs before damaged ones.
+    pub fn nth_arrangement(&self, k: u128) -> Option<Row> {
+        self.nth_with_table(k, &self.arrangement_table())
     }
-}
 
-#[cached]
-fn count_solutions(record: Vec<Condition>, groups: Vec<usize>) -> usize {
-    // If there are no more groups and no more "#", then successful.
-    let Some(next_group) = groups.first() else {
-        if record.iter().any(|c| c == &Condition::Damaged) {
-            return 0;
-        } else {
-            return 1;
+    fn nth_with_table(&self, mut k: u128, table: &[Vec<u128>]) -> Option<Row> {
+        if k >= table[0][0] {
+            return None;
         }
-    };
-
-    // If there are no more items left in record, then unsuccessful.
-    let Some(next_cond) = record.first() else {
-        return 0;
-    };
-
-    match next_cond {
-        Condition::Damaged => add_damaged(record.clone(), groups.clone(), *next_group),
-        Condition::Operational => add_operational(record.clone(), groups.clone()),
-        Condition::Unknown => {
-            add_damaged(record.clone(), groups.clone(), *next_group)
-                + add_operational(record.clone(), groups.clone())
+        let (n, m) = (self.conditions.len(), self.groups.len());
+        let mut conditions = Vec::with_capacity(n);
+        let (mut i, mut j) = (0, 0);
+        while i < n {
+            let n_operational = match self.conditions[i] {
+                Condition::Damaged => 0,
+                _ => table[i + 1][j],
+            };
+            if k < n_operational {
+                conditions.push(Condition::Operational);
+                i += 1;
+            } else {
+                k -= n_operational;
+                let len = self.groups[j];
+                conditions.extend(std::iter::repeat_n(Condition::Damaged, len));
+                if i + len < n {
+                    conditions.push(Condition::Operational);
+                }
+                i += len + 1;
+                j += 1;
+            }
         }
+        debug_assert_eq!(j, m);
+        Some(Row {
+            conditions,
+            groups: self.groups.clone(),
+        })
+    }
+
+    // Every arrangement, in the order of `nth_arrangement`.
+    pub fn arrangements(&self) -> impl Iterator<Item = Row> + '_ {
+        let table = self.arrangement_table();
+        let count = table[0][0];
+        (0..count).map_while(move |k| self.nth_with_table(k, &table))
     }
 }
 
-pub fn puzzle_1(input: &str) -> Result<usize, PuzzleErr> {
-    Ok(parse_input(input)?
-        .iter()
-        .map(|r| count_solutions(r.conditions.clone(), r.groups.clone()))
-        .sum())
+fn parse_input(input: &str) -> Result<Vec<Row>, PuzzleErr> {
+    input
+        .trim()
+        .lines()
+        .map(Row::try_from)
+        .collect::<Result<Vec<_>, PuzzleErr>>()
 }
 
-fn parse_and_expand_input(input: &str, n_reps: usize) -> Result<Vec<Row>, PuzzleErr> {
+pub fn parse_and_expand_input(input: &str, n_reps: usize) -> Result<Vec<Row>, PuzzleErr> {
     Ok(parse_input(input)?
         .iter()
-        .map(|r| {
-            let mut c = (0..n_reps)
-                .map(|_| {
-                    let mut new_c = r.conditions.clone();
-                    new_c.push(Condition::Unknown);
-                    new_c
-                })
-                .concat();
-            let _ = c.pop();
-            let g = (0..n_reps).map(|_| r.groups.clone()).concat();
-            Row {
-                conditions: c,
-                groups: g,
-            }
-        })
+        .map(|r| r.unfold(n_reps))
         .collect::<Vec<Row>>())
 }
 
-pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
-    Ok(parse_and_expand_input(input, 5)?
+pub fn count_all_arrangements(input: &str, n_reps: usize) -> Result<u128, PuzzleErr> {
+    Ok(parse_and_expand_input(input, n_reps)?
         .iter()
-        .map(|r| count_solutions(r.conditions.clone(), r.groups.clone()))
+        .map(|r| r.count_arrangements())
         .sum())
-    // Ok(0)
+}
+
+pub fn puzzle_1(input: &str) -> Result<u128, PuzzleErr> {
+    count_all_arrangements(input, 1)
+}
+
+pub fn puzzle_2(input: &str) -> Result<u128, PuzzleErr> {
+    count_all_arrangements(input, 5)
+}
+
+// Print each unfolded row with its number of arrangements and up to `n_show` of them.
+pub fn print_arrangements(data_dir: &str, n_reps: usize, n_show: usize) {
+    let data = load(data_dir, 12, None);
+    let rows = match parse_and_expand_input(&data, n_reps) {
+        Ok(rows) => rows,
+        Err(e) => panic!("Failed to parse input: {}", e),
+    };
+    let mut total = 0;
+    for row in rows.iter() {
+        let count = row.count_arrangements();
+        total += count;
+        println!("{}  ({} arrangements)", row, count);
+        for arrangement in row.arrangements().take(n_show) {
+            println!("  {}", arrangement);
+        }
+    }
+    println!("Total arrangements: {}", total);
 }
 
 pub fn main(data_dir: &str) {
@@ -1,16 +1,17 @@
 use crate::data::load;
-use itertools::Itertools;
-use ndarray::{prelude::*, Zip};
-use std::{cmp, iter::zip};
+use ndarray::prelude::*;
+use std::{cmp, fmt::Display};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleErr {
     #[error("Input parsing error.")]
     ParseInputError,
+    #[error("No reflection found for pattern {}.", .0)]
+    NoReflection(usize),
 }
 
-fn parse_grid(grid_str: &str) -> Result<Array2<bool>, PuzzleErr> {
+pub fn parse_grid(grid_str: &str) -> Result<Array2<bool>, PuzzleErr> {
     let bools = grid_str
         .trim()
         .lines()
@@ -26,11 +27,11 @@ fn parse_grid(grid_str: &str) -> Result<Array2<bool>, PuzzleErr> {
         })
         .collect::<Result<Vec<_>, PuzzleErr>>()?;
     let h = bools.len();
-    let w = bools.first().unwrap().len();
-    Ok(Array2::from_shape_vec((h, w), bools.concat()).unwrap())
+    let w = bools.first().ok_or(PuzzleErr::ParseInputError)?.len();
+    Array2::from_shape_vec((h, w), bools.concat()).map_err(|_| PuzzleErr::ParseInputError)
 }
 
-fn parse_input(input: &str) -> Result<Vec<Array2<bool>>, PuzzleErr> {
+pub fn parse_input(input: &str) -> Result<Vec<Array2<bool>>, PuzzleErr> {
     input
         .trim()
         .split("\n\n")
@@ -38,55 +39,128 @@ fn parse_input(input: &str) -> Result<Vec<Array2<bool>>, PuzzleErr> {
         .collect::<Result<Vec<_>, PuzzleErr>>()
 }
 
-fn hflip<T: Clone>(a: &Array2<T>) -> Array2<T> {
-    Array2::from_shape_vec(
-        a.raw_dim(),
-        a.axis_iter(Axis(0)).rev().map(|a| a.to_vec()).concat(),
-    )
-    .unwrap()
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    // A line between two rows.
+    Horizontal,
+    // A line between two columns.
+    Vertical,
 }
 
-fn is_mirror_around(grid: &Array2<bool>, r: usize, with_smudge: bool) -> bool {
-    let max_a1 = r + 1;
-    let max_a2 = (grid.nrows() - 1) - r;
-    let m = cmp::min(max_a1, max_a2);
-    let a1 = grid.slice(s![(r + 1 - m)..(r + 1), ..]);
-    let a2 = grid.slice(s![(r + 1)..(r + 1 + m), ..]).to_owned();
-    if with_smudge {
-        Zip::from(&a1)
-            .and(&hflip(&a2))
-            .map_collect(|x, y| if x == y { 0 } else { 1 })
-            .sum()
-            == 1
-    } else {
-        a1 == hflip(&a2)
+// A pair of mirrored cells that differ; flipping either one removes the mismatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Smudge {
+    pub cell: (usize, usize),
+    pub mirror: (usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub orientation: Orientation,
+    // Number of rows above (or columns left of) the line.
+    pub position: usize,
+    // Every mismatched pair, with `cell` on the side above or left of the line.
+    pub smudges: Vec<Smudge>,
+}
+
+impl Reflection {
+    pub fn summary(&self) -> usize {
+        match self.orientation {
+            Orientation::Horizontal => self.position * 100,
+            Orientation::Vertical => self.position,
+        }
+    }
+}
+
+impl Display for Reflection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (line, units) = match self.orientation {
+            Orientation::Horizontal => ("horizontal", "rows"),
+            Orientation::Vertical => ("vertical", "columns"),
+        };
+        write!(
+            f,
+            "{} line after {} {} with {} smudge(s)",
+            line,
+            self.position,
+            units,
+            self.smudges.len()
+        )?;
+        for s in self.smudges.iter() {
+            write!(
+                f,
+                "\n  fix ({}, {}) or its mirror ({}, {})",
+                s.cell.0, s.cell.1, s.mirror.0, s.mirror.1
+            )?;
+        }
+        Ok(())
     }
 }
 
-fn find_horizontal_mirror(grid: &Array2<bool>, with_smudge: bool) -> Option<usize> {
-    (0..(grid.nrows() - 1)).find(|&r| is_mirror_around(grid, r, with_smudge))
+// Mismatched cells when reflecting rows about the line after `position` rows.
+fn row_smudges(grid: &Array2<bool>, position: usize) -> Vec<Smudge> {
+    let m = cmp::min(position, grid.nrows() - position);
+    (0..m)
+        .flat_map(|i| {
+            let (above, below) = (position - 1 - i, position + i);
+            (0..grid.ncols())
+                .filter(move |c| grid[[above, *c]] != grid[[below, *c]])
+                .map(move |c| Smudge {
+                    cell: (above, c),
+                    mirror: (below, c),
+                })
+        })
+        .collect()
+}
+
+// Every horizontal and then every vertical reflection line, with its mismatches.
+pub fn reflections(grid: &Array2<bool>) -> Vec<Reflection> {
+    let horizontal = (1..grid.nrows()).map(|p| Reflection {
+        orientation: Orientation::Horizontal,
+        position: p,
+        smudges: row_smudges(grid, p),
+    });
+    let transposed = grid.t().to_owned();
+    let vertical = (1..grid.ncols()).map(|p| Reflection {
+        orientation: Orientation::Vertical,
+        position: p,
+        smudges: row_smudges(&transposed, p)
+            .into_iter()
+            .map(|s| Smudge {
+                cell: (s.cell.1, s.cell.0),
+                mirror: (s.mirror.1, s.mirror.0),
+            })
+            .collect(),
+    });
+    horizontal.chain(vertical).collect()
+}
+
+pub fn reflections_with_smudges(grid: &Array2<bool>, n_smudges: usize) -> Vec<Reflection> {
+    reflections(grid)
+        .into_iter()
+        .filter(|r| r.smudges.len() == n_smudges)
+        .collect()
 }
 
-fn _solve(input: &str, with_smudge: bool) -> Result<usize, PuzzleErr> {
-    let grids = parse_input(input)?;
-    let h_results = grids
+fn _solve(input: &str, n_smudges: usize) -> Result<usize, PuzzleErr> {
+    parse_input(input)?
         .iter()
-        .map(|g| find_horizontal_mirror(g, with_smudge))
-        .collect::<Vec<_>>();
-    Ok(zip(grids.iter(), h_results)
-        .map(|(g, h_res)| match h_res {
-            Some(x) => (x + 1) * 100,
-            None => find_horizontal_mirror(&g.t().to_owned(), with_smudge).unwrap() + 1,
+        .enumerate()
+        .map(|(i, g)| {
+            reflections_with_smudges(g, n_smudges)
+                .first()
+                .map(|r| r.summary())
+                .ok_or(PuzzleErr::NoReflection(i))
         })
-        .sum())
+        .sum()
 }
 
 pub fn puzzle_1(input: &str) -> Result<usize, PuzzleErr> {
-    _solve(input, false)
+    _solve(input, 0)
 }
 
 pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
-    _solve(input, true)
+    _solve(input, 1)
 }
 
 pub fn main(data_dir: &str) {
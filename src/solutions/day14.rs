@@ -1,6 +1,8 @@
 use crate::data::load;
-use cached::proc_macro::cached;
-use std::collections::{HashMap, HashSet};
+use crate::memo::{CacheStats, Memo};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::Display;
 use std::hash::{Hash, Hasher};
 use thiserror::Error;
 
@@ -8,156 +10,333 @@ use thiserror::Error;
 pub enum PuzzleErr {
     #[error("Input parsing error.")]
     ParseInputError,
+    #[error("Platform is wider than {} columns.", u128::BITS)]
+    TooWide,
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct Coord {
-    r: isize,
-    c: isize,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    West,
+    South,
+    East,
 }
 
-impl Coord {
-    fn new(r: isize, c: isize) -> Self {
-        Coord { r, c }
-    }
+// Each row is a bitset with column `c` stored in bit `c`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    round: Vec<u128>,
+    square: Vec<u128>,
+    width: usize,
 }
 
-impl Coord {
-    fn rot90(&self, r_max: &isize) -> Coord {
-        Coord {
-            r: self.c,
-            c: r_max - self.r,
+impl Platform {
+    pub fn parse(input: &str) -> Result<Self, PuzzleErr> {
+        let lines = input.trim().lines().map(|l| l.trim()).collect::<Vec<_>>();
+        let width = lines.first().ok_or(PuzzleErr::ParseInputError)?.len();
+        if width > u128::BITS as usize {
+            return Err(PuzzleErr::TooWide);
         }
+        let mut round = vec![0; lines.len()];
+        let mut square = vec![0; lines.len()];
+        for (r, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(PuzzleErr::ParseInputError);
+            }
+            for (c, item) in line.chars().enumerate() {
+                match item {
+                    'O' => round[r] |= 1 << c,
+                    '#' => square[r] |= 1 << c,
+                    '.' => (),
+                    _ => return Err(PuzzleErr::ParseInputError),
+                }
+            }
+        }
+        Ok(Self {
+            round,
+            square,
+            width,
+        })
     }
-}
-
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-struct Rocks {
-    round: HashSet<Coord>,
-    square: HashSet<Coord>,
-    height: isize,
-    width: isize,
-}
 
-impl Hash for Rocks {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        let mut v_round = self.round.iter().collect::<Vec<_>>();
-        v_round.sort();
-        v_round.hash(state);
-        let mut v_square = self.square.iter().collect::<Vec<_>>();
-        v_square.sort();
-        v_square.hash(state);
+    pub fn height(&self) -> usize {
+        self.round.len()
     }
-}
 
-fn parse_input(input: &str) -> Rocks {
-    let mut rocks = Rocks::default();
+    pub fn width(&self) -> usize {
+        self.width
+    }
 
-    let lines = input.trim().lines().collect::<Vec<_>>();
-    rocks.height = lines.len() as isize;
-    rocks.width = lines.first().unwrap().len() as isize;
+    // Move every round rock from `from` into `to` where that space is empty.
+    fn slide_rows(&mut self, from: usize, to: usize) -> bool {
+        let moving = self.round[from] & !(self.round[to] | self.square[to]);
+        self.round[from] &= !moving;
+        self.round[to] |= moving;
+        moving != 0
+    }
 
-    lines.into_iter().enumerate().for_each(|(r, line)| {
-        line.trim().chars().enumerate().for_each(|(c, item)| {
-            let coord = Coord::new(r as isize, c as isize);
-            match item {
-                'O' => rocks.round.insert(coord),
-                '#' => rocks.square.insert(coord),
-                _ => false,
+    // The round rocks of one row after sliding them one column at a time until none
+    // can move.
+    fn slide_row(mut round: u128, square: u128, width: usize, west: bool) -> u128 {
+        let mask = u128::MAX >> (u128::BITS as usize - width);
+        loop {
+            let empty = !(round | square) & mask;
+            let moving = match west {
+                true => round & (empty << 1),
+                false => round & (empty >> 1),
             };
-        })
-    });
+            if moving == 0 {
+                return round;
+            }
+            round &= !moving;
+            round |= match west {
+                true => moving >> 1,
+                false => moving << 1,
+            };
+        }
+    }
 
-    rocks
-}
+    // Tilt the platform, with `slide_row` working out where each row's rocks end up when
+    // tilting west or east.
+    fn tilt_with(
+        &mut self,
+        dir: Direction,
+        slide_row: &mut impl FnMut(u128, u128, usize, bool) -> u128,
+    ) {
+        let height = self.height();
+        match dir {
+            Direction::North => {
+                while (1..height).fold(false, |moved, r| self.slide_rows(r, r - 1) | moved) {}
+            }
+            Direction::South => {
+                while (1..height)
+                    .rev()
+                    .fold(false, |moved, r| self.slide_rows(r - 1, r) | moved)
+                {}
+            }
+            Direction::West | Direction::East => {
+                for r in 0..height {
+                    let west = dir == Direction::West;
+                    self.round[r] = slide_row(self.round[r], self.square[r], self.width, west);
+                }
+            }
+        }
+    }
 
-fn find_north_position(coord: &Coord, rocks: &Rocks) -> Coord {
-    let mut prev_coord = *coord;
-    for r in (0..coord.r).rev() {
-        let next_coord = Coord::new(r, coord.c);
-        if rocks.square.contains(&next_coord) | rocks.round.contains(&next_coord) {
-            break;
+    pub fn tilt(&mut self, dir: Direction) {
+        self.tilt_with(dir, &mut Self::slide_row);
+    }
+
+    fn spin_cycle_with(&mut self, slide_row: &mut impl FnMut(u128, u128, usize, bool) -> u128) {
+        for dir in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            self.tilt_with(dir, slide_row);
         }
-        prev_coord = next_coord;
     }
-    prev_coord
+
+    pub fn spin_cycle(&mut self) {
+        self.spin_cycle_with(&mut Self::slide_row);
+    }
+
+    // Each round rock adds the number of rows from it to the south edge, inclusive.
+    pub fn north_load(&self) -> usize {
+        self.round
+            .iter()
+            .enumerate()
+            .map(|(r, row)| (self.height() - r) * row.count_ones() as usize)
+            .sum()
+    }
+
+    // Hash of the round rocks (the square rocks never move).
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.round.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
-#[cached]
-fn tilt_north(rocks: Rocks) -> Rocks {
-    let mut new_rocks = rocks.clone();
-    for r in 0..rocks.height {
-        for c in 0..rocks.width {
-            let coord = Coord::new(r, c);
-            if new_rocks.round.contains(&coord) {
-                new_rocks.round.remove(&coord);
-                new_rocks
-                    .round
-                    .insert(find_north_position(&coord, &new_rocks));
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (r, (round, square)) in self.round.iter().zip(self.square.iter()).enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for c in 0..self.width {
+                let item = match ((round >> c) & 1, (square >> c) & 1) {
+                    (1, _) => 'O',
+                    (_, 1) => '#',
+                    _ => '.',
+                };
+                write!(f, "{}", item)?;
             }
         }
+        Ok(())
     }
-    new_rocks
 }
 
-fn calc_total_load(rocks: &Rocks) -> isize {
-    rocks.round.iter().map(|coord| rocks.height - coord.r).sum()
+// Every platform state from the start until the spin cycle first repeats a state.
+#[derive(Debug, Clone)]
+pub struct SpinHistory {
+    // `platforms[i]` is the platform after `i` spin cycles.
+    platforms: Vec<Platform>,
+    // North load after each number of spin cycles.
+    pub loads: Vec<usize>,
+    // Number of cycles before the platform enters its loop.
+    pub loop_start: usize,
+    pub period: usize,
 }
 
-pub fn puzzle_1(input: &str) -> isize {
-    let mut rocks = parse_input(input);
-    rocks = tilt_north(rocks);
-    calc_total_load(&rocks)
+impl SpinHistory {
+    pub fn new(platform: Platform) -> Self {
+        SpinCycler::new().history(platform)
+    }
+
+    fn build(platform: Platform, cycler: &mut SpinCycler) -> Self {
+        let mut seen = HashMap::<u64, Vec<usize>>::new();
+        let mut platforms = Vec::<Platform>::new();
+        let mut platform = platform;
+        loop {
+            let i = platforms.len();
+            let matches = seen.entry(platform.fingerprint()).or_default();
+            if let Some(j) = matches.iter().find(|j| platforms[**j] == platform) {
+                let loads = platforms.iter().map(|p| p.north_load()).collect();
+                log::info!("Day 14 spin cycle loops from cycle {} every {}.", j, i - j);
+                return Self {
+                    loads,
+                    loop_start: *j,
+                    period: i - j,
+                    platforms,
+                };
+            }
+            matches.push(i);
+            platforms.push(platform.clone());
+            cycler.spin_cycle(&mut platform);
+        }
+    }
+
+    // The earliest cycle with the same platform as after `n_cycles` spin cycles.
+    pub fn equivalent_cycle(&self, n_cycles: usize) -> usize {
+        if n_cycles < self.loop_start {
+            n_cycles
+        } else {
+            self.loop_start + (n_cycles - self.loop_start) % self.period
+        }
+    }
+
+    pub fn platform_at(&self, n_cycles: usize) -> &Platform {
+        &self.platforms[self.equivalent_cycle(n_cycles)]
+    }
+
+    pub fn load_at(&self, n_cycles: usize) -> usize {
+        self.loads[self.equivalent_cycle(n_cycles)]
+    }
 }
 
-#[cached]
-fn rotate_rocks(rocks: Rocks) -> Rocks {
-    let mut new_rocks = rocks.clone();
-    new_rocks.round = new_rocks
-        .round
-        .iter()
-        .map(|c| c.rot90(&(new_rocks.height - 1)))
-        .collect();
-    new_rocks.square = new_rocks
-        .square
-        .iter()
-        .map(|c| c.rot90(&(new_rocks.height - 1)))
-        .collect();
-    (new_rocks.height, new_rocks.width) = (new_rocks.width, new_rocks.height);
-    new_rocks
+// Spins platforms, remembering where the rocks of every row end up when it slides
+// sideways, for as long as the cycler lives.
+#[derive(Debug, Default)]
+pub struct SpinCycler {
+    row_memo: Memo<(u128, u128, usize, bool), u128>,
 }
 
-#[cached]
-fn rotation_cycle(mut rocks: Rocks) -> Rocks {
-    for _ in 0..4 {
-        rocks = rotate_rocks(tilt_north(rocks));
+impl SpinCycler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn slide_row(&mut self, round: u128, square: u128, width: usize, west: bool) -> u128 {
+        let key = (round, square, width, west);
+        match self.row_memo.get(&key) {
+            Some(slid) => slid,
+            None => self
+                .row_memo
+                .insert(key, Platform::slide_row(round, square, width, west)),
+        }
     }
-    rocks
-}
 
-pub fn puzzle_2(input: &str, n_cycles: usize) -> isize {
-    let mut rocks = parse_input(input);
+    pub fn tilt(&mut self, platform: &mut Platform, dir: Direction) {
+        platform.tilt_with(dir, &mut |r, s, w, west| self.slide_row(r, s, w, west));
+    }
 
-    let mut prev_cycle_cache: HashMap<Rocks, Vec<usize>> = HashMap::new();
-    let mut i = 0;
-    while i != n_cycles {
-        rocks = rotation_cycle(rocks.clone());
-        i += 1;
+    pub fn spin_cycle(&mut self, platform: &mut Platform) {
+        platform.spin_cycle_with(&mut |r, s, w, west| self.slide_row(r, s, w, west));
+    }
 
-        let prev_i = prev_cycle_cache.entry(rocks.clone()).or_default();
+    pub fn history(&mut self, platform: Platform) -> SpinHistory {
+        SpinHistory::build(platform, self)
+    }
 
-        if let Some(new_i) = prev_i
-            .iter()
-            .map(|j| i + (i - j))
-            .filter(|new_i| new_i <= &n_cycles)
-            .max()
-        {
-            i = new_i;
+    // Hit and miss counts of the row slide cache.
+    pub fn stats(&self) -> CacheStats {
+        self.row_memo.stats()
+    }
+
+    pub fn clear(&mut self) {
+        self.row_memo.clear();
+    }
+}
+
+impl Display for SpinHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Pre-period: {} cycles", self.loop_start)?;
+        writeln!(f, "Period: {} cycles", self.period)?;
+        writeln!(f, "cycle  load")?;
+        for (i, load) in self.loads.iter().enumerate() {
+            let marker = if i == self.loop_start {
+                "  <- loop start"
+            } else {
+                ""
+            };
+            writeln!(f, "{:>5}  {}{}", i, load, marker)?;
         }
-        prev_i.push(i);
+        Ok(())
     }
+}
 
-    calc_total_load(&rocks)
+pub fn spin_history(input: &str) -> Result<SpinHistory, PuzzleErr> {
+    let mut cycler = SpinCycler::new();
+    let history = cycler.history(Platform::parse(input)?);
+    log::info!("Day 14 row slide cache: {}.", cycler.stats());
+    Ok(history)
+}
+
+// Print the spin cycle history, the load after `n_cycles`, and optionally the platform
+// after `render_cycle` spin cycles.
+pub fn explore_cycles(data_dir: &str, n_cycles: usize, render_cycle: Option<usize>) {
+    let data = load(data_dir, 14, None);
+    let history = match spin_history(&data) {
+        Ok(h) => h,
+        Err(e) => panic!("Failed to parse input: {}", e),
+    };
+    print!("{}", history);
+    println!(
+        "Cycle {} is equivalent to cycle {} with load {}.",
+        n_cycles,
+        history.equivalent_cycle(n_cycles),
+        history.load_at(n_cycles)
+    );
+    if let Some(c) = render_cycle {
+        println!(
+            "Platform after {} cycles (equivalent to cycle {}):",
+            c,
+            history.equivalent_cycle(c)
+        );
+        println!("{}", history.platform_at(c));
+    }
+}
+
+pub fn puzzle_1(input: &str) -> Result<usize, PuzzleErr> {
+    let mut platform = Platform::parse(input)?;
+    platform.tilt(Direction::North);
+    Ok(platform.north_load())
+}
+
+pub fn puzzle_2(input: &str, n_cycles: usize) -> Result<usize, PuzzleErr> {
+    Ok(spin_history(input)?.load_at(n_cycles))
 }
 
 pub fn main(data_dir: &str) {
@@ -166,12 +345,17 @@ pub fn main(data_dir: &str) {
 
     // Puzzle 1.
     let answer_1 = puzzle_1(&data);
-    println!(" Puzzle 1: {}", answer_1);
-    assert_eq!(answer_1, 112046);
+    match answer_1 {
+        Ok(x) => println!(" Puzzle 1: {}", x),
+        Err(e) => panic!("No solution to puzzle 1: {}.", e),
+    }
+    assert_eq!(answer_1, Ok(112046));
 
     // Puzzle 2.
     let answer_2 = puzzle_2(&data, 1000000000);
-    println!(" Puzzle 2: {}", answer_2);
-    assert_eq!(answer_2, 104619);
+    match answer_2 {
+        Ok(x) => println!(" Puzzle 2: {}", x),
+        Err(e) => panic!("No solution to puzzle 2: {}", e),
+    }
+    assert_eq!(answer_2, Ok(104619));
 }
-// 104639 (too high)
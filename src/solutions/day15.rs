@@ -1,88 +1,252 @@
 use crate::data::load;
+use std::fmt::Display;
+use std::io::{BufReader, Bytes, Read};
+use thiserror::Error;
 
-fn ascii_hash(s: &str) -> usize {
-    s.trim()
-        .chars()
-        .map(|c| c as u8 as usize)
-        .fold(0, |a, b| ((a + b) * 17) % 256)
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PuzzleErr {
+    #[error("Unknown operation in step: {}.", .0)]
+    UnknownOperation(String),
+    #[error("Integer parsing error.")]
+    ParseIntError(#[from] std::num::ParseIntError),
+    #[error("A lens box simulator needs at least one box.")]
+    NoBoxes,
+    #[error("Step label is not valid UTF-8.")]
+    Utf8Error(#[from] std::str::Utf8Error),
+    #[error("Failed to read the initialization sequence: {}.", .0)]
+    ReadError(String),
+}
+
+fn hash_byte(current: usize, b: u8) -> usize {
+    ((current + b as usize) * 17) % 256
+}
+
+pub fn ascii_hash(s: &str) -> usize {
+    s.trim().bytes().fold(0, hash_byte)
+}
+
+// Sum of the hashes of every step, read in a single pass without storing any step.
+// Newlines and other whitespace are ignored.
+pub fn hash_sum<R: Read>(reader: R) -> Result<usize, PuzzleErr> {
+    let (mut total, mut current) = (0, 0);
+    for b in BufReader::new(reader).bytes() {
+        match b.map_err(|e| PuzzleErr::ReadError(e.to_string()))? {
+            b',' => total += std::mem::take(&mut current),
+            b if b.is_ascii_whitespace() => (),
+            b => current = hash_byte(current, b),
+        }
+    }
+    Ok(total + current)
 }
 
 pub fn puzzle_1(input: &str) -> usize {
-    input.split(',').map(ascii_hash).sum()
+    hash_sum(input.as_bytes()).unwrap()
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Lens<'a> {
-    label: &'a str,
-    focal_len: Option<usize>,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lens {
+    pub label: String,
+    pub focal_len: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Operation {
-    D,
-    E,
+pub enum Operation {
+    Remove,
+    Insert(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub label: String,
+    pub op: Operation,
 }
 
-impl From<&char> for Operation {
-    fn from(value: &char) -> Self {
-        match value {
-            '-' => Operation::D,
-            '=' => Operation::E,
-            _ => panic!("Unkown op: {}", value),
+impl TryFrom<&str> for Step {
+    type Error = PuzzleErr;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let step = value.trim();
+        if let Some(label) = step.strip_suffix('-') {
+            return Ok(Step {
+                label: label.to_string(),
+                op: Operation::Remove,
+            });
+        }
+        match step.split_once('=') {
+            Some((label, focal_len)) => Ok(Step {
+                label: label.to_string(),
+                op: Operation::Insert(focal_len.parse::<usize>()?),
+            }),
+            None => Err(PuzzleErr::UnknownOperation(step.to_string())),
         }
     }
 }
 
-fn calc_focussing_power(boxes: &[Vec<Lens>]) -> usize {
-    boxes
-        .iter()
-        .enumerate()
-        .map(|(b_idx, b)| {
-            b.iter()
-                .enumerate()
-                .map(|(lens_idx, lens)| (b_idx + 1) * (lens_idx + 1) * lens.focal_len.unwrap())
-                .sum::<usize>()
-        })
-        .sum()
-}
-
-fn parse_step(step: &str) -> (Lens, Operation) {
-    if step.contains('-') {
-        return (
-            Lens {
-                label: step.split('-').collect::<Vec<_>>()[0],
-                focal_len: None,
-            },
-            Operation::D,
-        );
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.op {
+            Operation::Remove => write!(f, "{}-", self.label),
+            Operation::Insert(focal_len) => write!(f, "{}={}", self.label, focal_len),
+        }
     }
-    let split = step.split('=').collect::<Vec<_>>();
-    let label = split[0];
-    let focal_len = Some(split[1].parse::<usize>().unwrap());
-    (Lens { label, focal_len }, Operation::E)
-}
-
-pub fn puzzle_2(input: &str) -> usize {
-    let mut boxes: Vec<Vec<Lens>> = (0..256).map(|_| Vec::new()).collect::<Vec<_>>();
-    for step in input.trim().split(',') {
-        let (lens, op) = parse_step(step);
-        let box_i = ascii_hash(lens.label);
-        let _box = boxes.get_mut(box_i).unwrap();
-        match op {
-            Operation::D => {
-                _box.retain(|l| l.label != lens.label);
+}
+
+// Reads steps one at a time, holding only the current step in memory.
+pub struct Steps<R: Read> {
+    bytes: Bytes<BufReader<R>>,
+    buffer: Vec<u8>,
+}
+
+impl<R: Read> Iterator for Steps<R> {
+    type Item = Result<Step, PuzzleErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        loop {
+            match self.bytes.next() {
+                Some(Err(e)) => return Some(Err(PuzzleErr::ReadError(e.to_string()))),
+                Some(Ok(b',')) if !self.buffer.is_empty() => break,
+                Some(Ok(b)) if (b == b',') | b.is_ascii_whitespace() => (),
+                Some(Ok(b)) => self.buffer.push(b),
+                None if self.buffer.is_empty() => return None,
+                None => break,
             }
-            Operation::E => {
-                if let Some(lens_i) = _box.iter().position(|l| l.label == lens.label) {
-                    let _lens = _box.get_mut(lens_i).unwrap();
-                    _lens.focal_len = lens.focal_len;
-                } else {
-                    _box.push(lens);
+        }
+        Some(
+            std::str::from_utf8(&self.buffer)
+                .map_err(PuzzleErr::from)
+                .and_then(Step::try_from),
+        )
+    }
+}
+
+pub fn parse_steps<R: Read>(reader: R) -> Steps<R> {
+    Steps {
+        bytes: BufReader::new(reader).bytes(),
+        buffer: Vec::new(),
+    }
+}
+
+// A row of boxes, each holding lenses in order; a lens goes in the box chosen by
+// hashing its label.
+#[derive(Debug, Clone)]
+pub struct LensBoxes {
+    boxes: Vec<Vec<Lens>>,
+    hash: fn(&str) -> usize,
+}
+
+impl Default for LensBoxes {
+    fn default() -> Self {
+        Self::new(256, ascii_hash).unwrap()
+    }
+}
+
+impl LensBoxes {
+    // `n_boxes` boxes, with lenses placed in box `hash(label) % n_boxes`.
+    pub fn new(n_boxes: usize, hash: fn(&str) -> usize) -> Result<Self, PuzzleErr> {
+        if n_boxes == 0 {
+            return Err(PuzzleErr::NoBoxes);
+        }
+        Ok(Self {
+            boxes: vec![Vec::new(); n_boxes],
+            hash,
+        })
+    }
+
+    pub fn boxes(&self) -> &[Vec<Lens>] {
+        &self.boxes
+    }
+
+    pub fn box_index(&self, label: &str) -> usize {
+        (self.hash)(label) % self.boxes.len()
+    }
+
+    pub fn apply(&mut self, step: &Step) {
+        let box_i = self.box_index(&step.label);
+        let lenses = &mut self.boxes[box_i];
+        match step.op {
+            Operation::Remove => lenses.retain(|l| l.label != step.label),
+            Operation::Insert(focal_len) => {
+                match lenses.iter_mut().find(|l| l.label == step.label) {
+                    Some(lens) => lens.focal_len = focal_len,
+                    None => lenses.push(Lens {
+                        label: step.label.clone(),
+                        focal_len,
+                    }),
                 }
             }
         }
     }
-    calc_focussing_power(&boxes)
+
+    pub fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .map(|(b_idx, b)| {
+                b.iter()
+                    .enumerate()
+                    .map(|(lens_idx, lens)| (b_idx + 1) * (lens_idx + 1) * lens.focal_len)
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+
+    // The state of the boxes after each step, paired with that step.
+    pub fn states<'a>(
+        self,
+        steps: impl Iterator<Item = Result<Step, PuzzleErr>> + 'a,
+    ) -> impl Iterator<Item = Result<(Step, LensBoxes), PuzzleErr>> + 'a {
+        steps.scan(self, |boxes, step| {
+            Some(step.map(|s| {
+                boxes.apply(&s);
+                (s, boxes.clone())
+            }))
+        })
+    }
+}
+
+// Lists the non-empty boxes as the puzzle text does, e.g. `Box 3: [ot 7] [ab 5]`.
+impl Display for LensBoxes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = self
+            .boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| !b.is_empty())
+            .map(|(i, b)| {
+                let lenses = b
+                    .iter()
+                    .map(|l| format!("[{} {}]", l.label, l.focal_len))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!("Box {}: {}", i, lenses)
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+// The boxes after every step of the initialization sequence.
+pub fn trace(input: &str, boxes: LensBoxes) -> Result<String, PuzzleErr> {
+    let blocks = boxes
+        .states(parse_steps(input.as_bytes()))
+        .map(|state| {
+            let (step, boxes) = state?;
+            Ok(format!("After \"{}\":\n{}", step, boxes))
+        })
+        .collect::<Result<Vec<_>, PuzzleErr>>()?;
+    Ok(blocks.join("\n\n"))
+}
+
+// Run every step from `reader` through the boxes, one step at a time.
+pub fn focusing_power<R: Read>(reader: R, mut boxes: LensBoxes) -> Result<usize, PuzzleErr> {
+    for step in parse_steps(reader) {
+        boxes.apply(&step?);
+    }
+    Ok(boxes.focusing_power())
+}
+
+pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
+    focusing_power(input.as_bytes(), LensBoxes::default())
 }
 
 pub fn main(data_dir: &str) {
@@ -96,6 +260,9 @@ pub fn main(data_dir: &str) {
 
     // Puzzle 2.
     let answer_2 = puzzle_2(&data);
-    println!(" Puzzle 2: {}", answer_2);
-    // assert_eq!(answer_2, Ok(30449))
+    match answer_2 {
+        Ok(x) => println!(" Puzzle 2: {}", x),
+        Err(e) => panic!("No solution to puzzle 2: {}", e),
+    }
+    assert_eq!(answer_2, Ok(295719))
 }
@@ -1,4 +1,5 @@
 use crate::data::load;
+use petgraph::{algo, graph::DiGraph};
 use std::{
     cmp,
     collections::{HashMap, HashSet},
@@ -9,38 +10,106 @@ use thiserror::Error;
 pub enum PuzzleErr {
     #[error("Input parsing error: {}.", .0)]
     ParseInputError(String),
+    #[error("Optical element config error: {}.", .0)]
+    ConfigError(String),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CaveObject {
-    Empty,     // "."
-    MirrorL,   // "\"
-    MirrorR,   // "/"
-    SplitterV, // "|"
-    SplitterH, // "-"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Coord {
+    r: i32,
+    c: i32,
 }
 
-impl TryFrom<&char> for CaveObject {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl TryFrom<&char> for Direction {
     type Error = PuzzleErr;
     fn try_from(value: &char) -> Result<Self, Self::Error> {
         match value {
-            '.' => Ok(CaveObject::Empty),
-            '\\' => Ok(CaveObject::MirrorL),
-            '/' => Ok(CaveObject::MirrorR),
-            '|' => Ok(CaveObject::SplitterV),
-            '-' => Ok(CaveObject::SplitterH),
-            _ => Err(PuzzleErr::ParseInputError(value.to_string())),
+            'U' => Ok(Direction::Up),
+            'D' => Ok(Direction::Down),
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(PuzzleErr::ConfigError(value.to_string())),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord {
-    r: i32,
-    c: i32,
+// The puzzle's elements: empty space, mirrors, and splitters.
+const DEFAULT_ELEMENTS: &str = r"
+. U:U D:D L:L R:R
+\ U:L D:R L:U R:D
+/ U:R D:L L:D R:U
+| U:U D:D L:UD R:UD
+- U:LR D:LR L:L R:R
+";
+
+// The outgoing beam directions for each element and incoming beam direction.
+//
+// Configured with one line per element: the element's symbol followed by a rule for
+// each incoming direction, e.g. `| U:U D:D L:UD R:UD`. A beam arriving from a
+// direction without a rule is absorbed. Blank lines and lines starting with `//` are
+// ignored, so any other character, including `#`, can be an element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementTable {
+    rules: HashMap<(char, Direction), Vec<Direction>>,
+    symbols: HashSet<char>,
+}
+
+impl Default for ElementTable {
+    fn default() -> Self {
+        Self::parse(DEFAULT_ELEMENTS).unwrap()
+    }
+}
+
+impl ElementTable {
+    pub fn parse(config: &str) -> Result<Self, PuzzleErr> {
+        let mut rules = HashMap::new();
+        let mut symbols = HashSet::new();
+        for line in config
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() & !l.starts_with("//"))
+        {
+            let mut fields = line.split_whitespace();
+            let symbol = fields.next().unwrap();
+            let mut symbol_chars = symbol.chars();
+            let (Some(symbol), None) = (symbol_chars.next(), symbol_chars.next()) else {
+                return Err(PuzzleErr::ConfigError(line.to_string()));
+            };
+            symbols.insert(symbol);
+            for rule in fields {
+                let Some((incoming, outgoing)) = rule.split_once(':') else {
+                    return Err(PuzzleErr::ConfigError(rule.to_string()));
+                };
+                let mut incoming_chars = incoming.chars();
+                let (Some(incoming), None) = (incoming_chars.next(), incoming_chars.next()) else {
+                    return Err(PuzzleErr::ConfigError(rule.to_string()));
+                };
+                let outgoing = outgoing
+                    .chars()
+                    .map(|d| Direction::try_from(&d))
+                    .collect::<Result<Vec<_>, PuzzleErr>>()?;
+                rules.insert((symbol, Direction::try_from(&incoming)?), outgoing);
+            }
+        }
+        Ok(Self { rules, symbols })
+    }
+
+    fn outgoing(&self, symbol: &char, incoming: &Direction) -> &[Direction] {
+        self.rules
+            .get(&(*symbol, *incoming))
+            .map_or(&[], |dirs| dirs.as_slice())
+    }
 }
 
-fn parse_input(input: &str) -> Result<HashMap<Coord, CaveObject>, PuzzleErr> {
+fn parse_input(input: &str, elements: &ElementTable) -> Result<HashMap<Coord, char>, PuzzleErr> {
     let mut grid = HashMap::new();
     for (r, line) in input.trim().lines().enumerate() {
         for (c, item) in line.trim().chars().enumerate() {
@@ -48,21 +117,15 @@ fn parse_input(input: &str) -> Result<HashMap<Coord, CaveObject>, PuzzleErr> {
                 r: r as i32,
                 c: c as i32,
             };
-            let obj = CaveObject::try_from(&item)?;
-            grid.insert(coord, obj);
+            if !elements.symbols.contains(&item) {
+                return Err(PuzzleErr::ParseInputError(item.to_string()));
+            }
+            grid.insert(coord, item);
         }
     }
     Ok(grid)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Beam {
     loc: Coord,
@@ -76,118 +139,253 @@ impl Beam {
 }
 
 impl Beam {
-    fn move_up(&self) -> Self {
+    fn move_dir(&self, dir: &Direction) -> Self {
         let mut b = *self;
-        b.dir = Direction::Up;
-        b.loc.r -= 1;
+        b.dir = *dir;
+        match dir {
+            Direction::Up => b.loc.r -= 1,
+            Direction::Down => b.loc.r += 1,
+            Direction::Left => b.loc.c -= 1,
+            Direction::Right => b.loc.c += 1,
+        };
         b
     }
+}
 
-    fn move_down(&self) -> Self {
-        let mut b = *self;
-        b.dir = Direction::Down;
-        b.loc.r += 1;
-        b
+fn decide_next_beam(beam: &Beam, element: &char, elements: &ElementTable) -> Vec<Beam> {
+    elements
+        .outgoing(element, &beam.dir)
+        .iter()
+        .map(|d| beam.move_dir(d))
+        .collect()
+}
+
+// The tiles a beam passes through until it is split, leaves the grid, or loops back on
+// itself, along with the beams leaving the splitter (if any).
+#[derive(Debug, Clone)]
+struct Segment {
+    tiles: Vec<usize>,
+    next: Vec<Beam>,
+}
+
+#[derive(Debug, Clone)]
+struct Contraption {
+    grid: HashMap<Coord, char>,
+    elements: ElementTable,
+    height: i32,
+    width: i32,
+}
+
+impl Contraption {
+    fn parse(input: &str, elements: &ElementTable) -> Result<Self, PuzzleErr> {
+        let grid = parse_input(input, elements)?;
+        let height = grid.keys().map(|c| c.r).max().unwrap() + 1;
+        let width = grid.keys().map(|c| c.c).max().unwrap() + 1;
+        Ok(Self {
+            grid,
+            elements: elements.clone(),
+            height,
+            width,
+        })
     }
 
-    fn move_left(&self) -> Self {
-        let mut b = *self;
-        b.dir = Direction::Left;
-        b.loc.c -= 1;
-        b
+    fn tile_index(&self, coord: &Coord) -> usize {
+        (coord.r * self.width + coord.c) as usize
     }
 
-    fn move_right(&self) -> Self {
-        let mut b = *self;
-        b.dir = Direction::Right;
-        b.loc.c += 1;
-        b
+    fn n_tiles(&self) -> usize {
+        (self.height * self.width) as usize
+    }
+
+    fn edge_beams(&self) -> Vec<Beam> {
+        let mut beams = Vec::new();
+        for r in 0..self.height {
+            beams.push(Beam::new(Coord { r, c: 0 }, Direction::Right));
+            beams.push(Beam::new(
+                Coord {
+                    r,
+                    c: self.width - 1,
+                },
+                Direction::Left,
+            ));
+        }
+        for c in 0..self.width {
+            beams.push(Beam::new(Coord { r: 0, c }, Direction::Down));
+            beams.push(Beam::new(
+                Coord {
+                    r: self.height - 1,
+                    c,
+                },
+                Direction::Up,
+            ));
+        }
+        beams
+    }
+
+    fn trace_segment(&self, beam: &Beam) -> Segment {
+        let mut tiles = Vec::new();
+        let mut seen = HashSet::new();
+        let mut beam = *beam;
+        loop {
+            let Some(element) = self.grid.get(&beam.loc) else {
+                return Segment {
+                    tiles,
+                    next: Vec::new(),
+                };
+            };
+            if !seen.insert(beam) {
+                return Segment {
+                    tiles,
+                    next: Vec::new(),
+                };
+            }
+            tiles.push(self.tile_index(&beam.loc));
+            let mut next_beams = decide_next_beam(&beam, element, &self.elements);
+            if next_beams.len() != 1 {
+                next_beams.retain(|b| self.grid.contains_key(&b.loc));
+                return Segment {
+                    tiles,
+                    next: next_beams,
+                };
+            }
+            beam = next_beams[0];
+        }
+    }
+
+    // Energized tiles (as a bitset) for each entry beam. Segments are traced once and
+    // shared between all entry beams. Groups of segments that feed into each other
+    // through splitters are collapsed into strongly connected components, so each
+    // component's energized tiles are computed once, after those of the components it
+    // feeds into.
+    fn energize(&self, entries: &[Beam]) -> Vec<Vec<u64>> {
+        let mut segment_graph = DiGraph::<Segment, ()>::new();
+        let mut nodes = HashMap::new();
+        let mut stack = entries.to_vec();
+        while let Some(beam) = stack.pop() {
+            if nodes.contains_key(&beam) {
+                continue;
+            }
+            let segment = self.trace_segment(&beam);
+            stack.extend(segment.next.iter().filter(|b| !nodes.contains_key(*b)));
+            nodes.insert(beam, segment_graph.add_node(segment));
+        }
+        for node in segment_graph.node_indices().collect::<Vec<_>>() {
+            for next in segment_graph[node].next.clone() {
+                segment_graph.add_edge(node, nodes[&next], ());
+            }
+        }
+
+        let n_words = self.n_tiles().div_ceil(64);
+        let mut component_of = HashMap::new();
+        let mut component_tiles: Vec<Vec<u64>> = Vec::new();
+        // Components are returned with downstream components first.
+        for (i, component) in algo::tarjan_scc(&segment_graph).iter().enumerate() {
+            let mut tiles = vec![0_u64; n_words];
+            for node in component.iter() {
+                component_of.insert(*node, i);
+                for t in segment_graph[*node].tiles.iter() {
+                    tiles[t / 64] |= 1 << (t % 64);
+                }
+            }
+            for node in component.iter() {
+                for next in segment_graph.neighbors(*node) {
+                    let j = component_of[&next];
+                    if j != i {
+                        for (w, x) in tiles.iter_mut().zip(component_tiles[j].iter()) {
+                            *w |= x;
+                        }
+                    }
+                }
+            }
+            component_tiles.push(tiles);
+        }
+
+        entries
+            .iter()
+            .map(|b| component_tiles[component_of[&nodes[b]]].clone())
+            .collect()
     }
 }
 
-fn decide_next_beam(beam: &Beam, cave_obj: &CaveObject) -> Vec<Beam> {
-    match cave_obj {
-        CaveObject::Empty => match beam.dir {
-            Direction::Up => [beam.move_up()].to_vec(),
-            Direction::Down => [beam.move_down()].to_vec(),
-            Direction::Left => [beam.move_left()].to_vec(),
-            Direction::Right => [beam.move_right()].to_vec(),
-        },
-        CaveObject::MirrorL => match beam.dir {
-            Direction::Up => [beam.move_left()].to_vec(),
-            Direction::Down => [beam.move_right()].to_vec(),
-            Direction::Left => [beam.move_up()].to_vec(),
-            Direction::Right => [beam.move_down()].to_vec(),
-        },
-        CaveObject::MirrorR => match beam.dir {
-            Direction::Up => [beam.move_right()].to_vec(),
-            Direction::Down => [beam.move_left()].to_vec(),
-            Direction::Left => [beam.move_down()].to_vec(),
-            Direction::Right => [beam.move_up()].to_vec(),
-        },
-        CaveObject::SplitterV => match beam.dir {
-            Direction::Up => [beam.move_up()].to_vec(),
-            Direction::Down => [beam.move_down()].to_vec(),
-            Direction::Left | Direction::Right => [beam.move_up(), beam.move_down()].to_vec(),
-        },
-        CaveObject::SplitterH => match beam.dir {
-            Direction::Up | Direction::Down => [beam.move_left(), beam.move_right()].to_vec(),
-            Direction::Left => [beam.move_left()].to_vec(),
-            Direction::Right => [beam.move_right()].to_vec(),
-        },
-    }
-}
-
-fn move_beam(beam: &Beam, grid: &HashMap<Coord, CaveObject>, beam_tracker: &mut HashSet<Beam>) {
-    if beam_tracker.contains(beam) {
-        return;
-    }
-    let Some(cave_obj) = grid.get(&beam.loc) else {
-        return;
-    };
-    beam_tracker.insert(*beam);
-    for next_beam in decide_next_beam(beam, cave_obj) {
-        move_beam(&next_beam, grid, beam_tracker);
-    }
-}
-
-fn count_energized_tiles(starting_beam: Beam, grid: &HashMap<Coord, CaveObject>) -> usize {
-    let mut energized_coords = HashSet::new();
-    move_beam(&starting_beam, grid, &mut energized_coords);
-    energized_coords
-        .iter()
-        .map(|b| b.loc)
-        .collect::<HashSet<_>>()
-        .len()
+fn count_tiles(tiles: &[u64]) -> usize {
+    tiles.iter().map(|w| w.count_ones() as usize).sum()
 }
 
-pub fn puzzle_1(input: &str) -> Result<usize, PuzzleErr> {
-    let grid = parse_input(input)?;
-    let beam = Beam::new(Coord { r: 0, c: 0 }, Direction::Right);
-    Ok(count_energized_tiles(beam, &grid))
+// For each tile, the number of edge entry points whose beam energizes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heatmap {
+    pub counts: Vec<Vec<usize>>,
 }
 
-pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
-    let grid = parse_input(input)?;
-    let mut max = 0;
-    let height = grid.keys().map(|c| c.r).max().unwrap();
-    let width = grid.keys().map(|c| c.c).max().unwrap();
-
-    for r in 0..=height {
-        for (c, dir) in [(0, Direction::Right), (width, Direction::Left)] {
-            let beam = Beam::new(Coord { r, c }, dir);
-            max = cmp::max(max, count_energized_tiles(beam, &grid));
-        }
+impl Heatmap {
+    pub fn to_csv(&self) -> String {
+        self.counts
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
-    for c in 0..=width {
-        for (r, dir) in [(0, Direction::Down), (height, Direction::Up)] {
-            let beam = Beam::new(Coord { r, c }, dir);
-            max = cmp::max(max, count_energized_tiles(beam, &grid));
+    // Plain-text greyscale PGM image, brighter for tiles energized more often.
+    pub fn to_pgm(&self) -> String {
+        let max = self.counts.iter().flatten().max().copied().unwrap_or(0);
+        let height = self.counts.len();
+        let width = self.counts.first().map_or(0, |row| row.len());
+        let mut lines = Vec::from_iter([
+            "P2".to_string(),
+            format!("{} {}", width, height),
+            format!("{}", cmp::max(max, 1)),
+        ]);
+        lines.extend(self.to_csv().lines().map(|l| l.replace(',', " ")));
+        lines.join("\n")
+    }
+}
+
+pub fn energized_heatmap(input: &str, elements: &ElementTable) -> Result<Heatmap, PuzzleErr> {
+    let contraption = Contraption::parse(input, elements)?;
+    let mut counts = vec![0; contraption.n_tiles()];
+    for tiles in contraption.energize(&contraption.edge_beams()) {
+        for (i, count) in counts.iter_mut().enumerate() {
+            if tiles[i / 64] & (1 << (i % 64)) != 0 {
+                *count += 1;
+            }
         }
     }
+    Ok(Heatmap {
+        counts: counts
+            .chunks(contraption.width as usize)
+            .map(|row| row.to_vec())
+            .collect(),
+    })
+}
 
-    Ok(max)
+pub fn count_energized_tiles(input: &str, elements: &ElementTable) -> Result<usize, PuzzleErr> {
+    let contraption = Contraption::parse(input, elements)?;
+    let beam = Beam::new(Coord { r: 0, c: 0 }, Direction::Right);
+    Ok(count_tiles(&contraption.energize(&[beam])[0]))
+}
+
+pub fn max_energized_tiles(input: &str, elements: &ElementTable) -> Result<usize, PuzzleErr> {
+    let contraption = Contraption::parse(input, elements)?;
+    Ok(contraption
+        .energize(&contraption.edge_beams())
+        .iter()
+        .map(|tiles| count_tiles(tiles))
+        .max()
+        .unwrap())
+}
+
+pub fn puzzle_1(input: &str) -> Result<usize, PuzzleErr> {
+    count_energized_tiles(input, &ElementTable::default())
+}
+
+pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
+    max_energized_tiles(input, &ElementTable::default())
 }
 
 pub fn main(data_dir: &str) {
@@ -1,13 +1,15 @@
 use crate::data::load;
+use crate::search;
 use num::Complex;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::HashMap;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleErr {
     #[error("Integer parsing error.")]
     ParseIntError(#[from] std::num::ParseIntError),
+    #[error("No path to the end of the grid.")]
+    NoPath,
 }
 
 fn parse_grid(input: &str) -> Result<HashMap<Complex<i32>, i32>, PuzzleErr> {
@@ -23,31 +25,62 @@ fn parse_grid(input: &str) -> Result<HashMap<Complex<i32>, i32>, PuzzleErr> {
     Ok(grid)
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct State {
-    coord: Complex<i32>,
-    prev_dir: Complex<i32>,
-    score: i32,
+fn turns(d: Complex<i32>) -> Vec<Complex<i32>> {
+    Vec::from_iter([Complex { re: 0, im: 1 } / d, Complex { re: 0, im: -1 } / d])
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .score
-            .cmp(&self.score)
-            .then_with(|| self.coord.re.cmp(&other.coord.re))
-            .then_with(|| self.coord.im.cmp(&other.coord.im))
+// A state is the current coordinate and the direction of the last move.
+type State = (Complex<i32>, Complex<i32>);
+
+fn next_states(
+    state: &State,
+    grid: &HashMap<Complex<i32>, i32>,
+    min_steps: i32,
+    max_steps: i32,
+) -> Vec<(State, i32)> {
+    let (coord, prev_dir) = *state;
+    let mut next = Vec::new();
+    for next_dir in turns(prev_dir) {
+        for i in min_steps..=max_steps {
+            if grid.contains_key(&(coord + (next_dir * i))) {
+                let y: i32 = (1..=i)
+                    .map(|j| grid.get(&(coord + next_dir * j)).unwrap())
+                    .sum();
+                next.push(((coord + next_dir * i, next_dir), y));
+            }
+        }
     }
+    next
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CruciblePath {
+    pub heat_loss: i32,
+    // Every block entered along the route, paired with the direction it was entered.
+    pub steps: Vec<State>,
+    // Blocks at which the crucible changed direction.
+    pub turns: Vec<Complex<i32>>,
 }
 
-fn turns(d: Complex<i32>) -> Vec<Complex<i32>> {
-    Vec::from_iter([Complex { re: 0, im: 1 } / d, Complex { re: 0, im: -1 } / d])
+fn expand_states(states: &[State]) -> (Vec<State>, Vec<Complex<i32>>) {
+    let mut steps = Vec::new();
+    for pair in states.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let mut coord = a.0;
+        while coord != b.0 {
+            coord += b.1;
+            steps.push((coord, b.1));
+        }
+    }
+    // Every state between the first and the last is a turn; a path that starts at the
+    // end has none.
+    let turns = states
+        .iter()
+        .skip(1)
+        .take(states.len().saturating_sub(2))
+        .map(|s| s.0)
+        .collect();
+    (steps, turns)
 }
 
 fn shortest_path(
@@ -56,71 +89,87 @@ fn shortest_path(
     grid: &HashMap<Complex<i32>, i32>,
     min_steps: i32,
     max_steps: i32,
-) -> i32 {
-    let mut queue = BinaryHeap::<State>::from_iter([
-        State {
-            coord: *start,
-            prev_dir: Complex { re: 1, im: 0 },
-            score: 0,
-        },
-        State {
-            coord: *start,
-            prev_dir: Complex { re: 0, im: 1 },
-            score: 0,
-        },
-    ]);
-    let mut visited = HashSet::<(Complex<i32>, Complex<i32>)>::new();
-    while !queue.is_empty() {
-        let state = queue.pop().unwrap();
-        if &state.coord == end {
-            return state.score;
-        }
-        if visited.contains(&(state.coord, state.prev_dir)) {
-            continue;
-        }
-        visited.insert((state.coord, state.prev_dir));
-
-        for next_dir in turns(state.prev_dir) {
-            for i in min_steps..=max_steps {
-                if grid.contains_key(&(state.coord + (next_dir * i))) {
-                    let y: i32 = (1..=i)
-                        .map(|j| grid.get(&(state.coord + next_dir * j)).unwrap())
-                        .sum();
-                    let new_state = State {
-                        coord: state.coord + next_dir * i,
-                        prev_dir: next_dir,
-                        score: state.score + y,
-                    };
-                    queue.push(new_state);
-                }
-            }
-        }
-    }
-    unreachable!();
+) -> Option<CruciblePath> {
+    let starts = [
+        (*start, Complex { re: 1, im: 0 }),
+        (*start, Complex { re: 0, im: 1 }),
+    ];
+    let (heat_loss, states) = search::astar(
+        starts,
+        |s| next_states(s, grid, min_steps, max_steps),
+        |s| search::manhattan((s.0.re, s.0.im), (end.re, end.im)),
+        |s| &s.0 == end,
+    )?;
+    let (steps, turns) = expand_states(&states);
+    Some(CruciblePath {
+        heat_loss,
+        steps,
+        turns,
+    })
 }
 
-pub fn puzzle_1(input: &str) -> Result<i32, PuzzleErr> {
-    let grid = parse_grid(input)?;
-    let start = Complex { re: 0, im: 0 };
+fn grid_corner(grid: &HashMap<Complex<i32>, i32>) -> Complex<i32> {
     let end_re = grid.keys().map(|x| x.re).max().unwrap();
     let end_im = grid.keys().map(|x| x.im).max().unwrap();
-    let end = Complex {
+    Complex {
         re: end_re,
         im: end_im,
-    };
-    Ok(shortest_path(&start, &end, &grid, 1, 3))
+    }
 }
 
-pub fn puzzle_2(input: &str) -> Result<i32, PuzzleErr> {
+pub fn crucible_path(
+    input: &str,
+    min_steps: i32,
+    max_steps: i32,
+) -> Result<CruciblePath, PuzzleErr> {
     let grid = parse_grid(input)?;
     let start = Complex { re: 0, im: 0 };
-    let end_re = grid.keys().map(|x| x.re).max().unwrap();
-    let end_im = grid.keys().map(|x| x.im).max().unwrap();
-    let end = Complex {
-        re: end_re,
-        im: end_im,
-    };
-    Ok(shortest_path(&start, &end, &grid, 4, 10))
+    let end = grid_corner(&grid);
+    let path = shortest_path(&start, &end, &grid, min_steps, max_steps).ok_or(PuzzleErr::NoPath)?;
+    log::debug!("Crucible route:\n{}", render_path(input, &path)?);
+    Ok(path)
+}
+
+fn direction_arrow(dir: &Complex<i32>) -> char {
+    match (dir.re, dir.im) {
+        (-1, 0) => '^',
+        (1, 0) => 'v',
+        (0, -1) => '<',
+        _ => '>',
+    }
+}
+
+// Draws the grid with each block on the route replaced by the direction it was entered.
+pub fn render_path(input: &str, path: &CruciblePath) -> Result<String, PuzzleErr> {
+    let grid = parse_grid(input)?;
+    let corner = grid_corner(&grid);
+    let arrows = path
+        .steps
+        .iter()
+        .map(|(coord, dir)| (*coord, direction_arrow(dir)))
+        .collect::<HashMap<_, _>>();
+    Ok((0..=corner.re)
+        .map(|r| {
+            (0..=corner.im)
+                .map(|c| {
+                    let coord = Complex::new(r, c);
+                    match arrows.get(&coord) {
+                        Some(a) => *a,
+                        None => char::from_digit(grid[&coord] as u32, 10).unwrap(),
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+pub fn puzzle_1(input: &str) -> Result<i32, PuzzleErr> {
+    Ok(crucible_path(input, 1, 3)?.heat_loss)
+}
+
+pub fn puzzle_2(input: &str) -> Result<i32, PuzzleErr> {
+    Ok(crucible_path(input, 4, 10)?.heat_loss)
 }
 
 pub fn main(data_dir: &str) {
@@ -1,8 +1,13 @@
 use crate::data::load;
+use lazy_static::lazy_static;
 use num::integer::div_floor;
 use regex::Regex;
-use std::i64;
-use std::{iter::zip, num::ParseIntError};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet, VecDeque},
+    iter::zip,
+    num::ParseIntError,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -11,9 +16,15 @@ pub enum PuzzleErr {
     ParseInputError(String),
     #[error("Integer parsing error.")]
     ParseIntError(#[from] ParseIntError),
+    #[error("Dig plan ends at {:?} instead of returning to the start.", .0)]
+    UnclosedPlan((i64, i64)),
+    #[error("Dig steps {} and {} cross or overlap.", .0, .1)]
+    SelfCrossing(usize, usize),
+    #[error("Lagoon is too large to render ({} cells).", .0)]
+    TooLarge(i64),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Direction {
     U,
     D,
@@ -34,26 +45,42 @@ impl TryFrom<&char> for Direction {
     }
 }
 
+type Rgb = (u8, u8, u8);
+
 #[derive(Debug, Clone)]
 struct Dig {
     dir: Direction,
     n: i64,
+    color: Option<Rgb>,
+}
+
+fn parse_color(hex: &str) -> Result<Rgb, PuzzleErr> {
+    let channel = |i: usize| u8::from_str_radix(&hex[i..(i + 2)], 16);
+    Ok((channel(0)?, channel(2)?, channel(4)?))
+}
+
+lazy_static! {
+    static ref DIG_RE: Regex =
+        Regex::new(r"^(?<dir>\w{1}) (?<n>\d+)( \(\#(?<color>[0-9a-fA-F]{6})\))?").unwrap();
+    static ref COLOR_RE: Regex = Regex::new(r"\(\#(?<color>.+)\)").unwrap();
 }
 
 fn line_to_dig(line: &str) -> Result<Dig, PuzzleErr> {
-    let re = Regex::new(r"^(?<dir>\w{1}) (?<n>\d+)").unwrap();
-    let Some(caps) = re.captures(line) else {
+    let Some(caps) = DIG_RE.captures(line.trim()) else {
         return Err(PuzzleErr::ParseInputError(line.trim().to_string()));
     };
     Ok(Dig {
         dir: Direction::try_from(&caps["dir"].chars().next().unwrap())?,
         n: caps["n"].parse::<i64>()?,
+        color: caps
+            .name("color")
+            .map(|c| parse_color(c.as_str()))
+            .transpose()?,
     })
 }
 
 fn line_to_dig_2(line: &str) -> Result<Dig, PuzzleErr> {
-    let re = Regex::new(r"\(\#(?<color>.+)\)").unwrap();
-    let Some(caps) = re.captures(line) else {
+    let Some(caps) = COLOR_RE.captures(line) else {
         return Err(PuzzleErr::ParseInputError(line.trim().to_string()));
     };
     let color = caps["color"].to_string();
@@ -62,10 +89,14 @@ fn line_to_dig_2(line: &str) -> Result<Dig, PuzzleErr> {
         '1' => Direction::D,
         '2' => Direction::L,
         '3' => Direction::U,
-        c => panic!("Unknown char: {}", c),
+        c => return Err(PuzzleErr::ParseInputError(c.to_string())),
     };
-    let n = i64::from_str_radix(&color.as_str()[..5], 16).unwrap();
-    Ok(Dig { dir, n })
+    let n = i64::from_str_radix(&color.as_str()[..5], 16)?;
+    Ok(Dig {
+        dir,
+        n,
+        color: None,
+    })
 }
 
 fn parse_input(
@@ -98,29 +129,219 @@ fn dig_plan_to_vertices(dig_plan: &[Dig]) -> Vec<(i64, i64)> {
     vertices
 }
 
+// Axis-aligned segments share a point if their bounding boxes overlap.
+fn segments_touch(a: (&(i64, i64), &(i64, i64)), b: (&(i64, i64), &(i64, i64))) -> bool {
+    let overlaps = |a0: i64, a1: i64, b0: i64, b1: i64| {
+        cmp::max(cmp::min(a0, a1), cmp::min(b0, b1)) <= cmp::min(cmp::max(a0, a1), cmp::max(b0, b1))
+    };
+    overlaps(a.0 .0, a.1 .0, b.0 .0, b.1 .0) & overlaps(a.0 .1, a.1 .1, b.0 .1, b.1 .1)
+}
+
+// The plan's vertices, checking that the trench returns to the start and that no two
+// steps cross, overlap, or touch other than where consecutive steps meet.
+fn validate_plan(dig_plan: &[Dig]) -> Result<Vec<(i64, i64)>, PuzzleErr> {
+    let vertices = dig_plan_to_vertices(dig_plan);
+    let end = *vertices.last().unwrap();
+    if end != (0, 0) {
+        return Err(PuzzleErr::UnclosedPlan(end));
+    }
+    let n = dig_plan.len();
+    let segment = |i: usize| (&vertices[i], &vertices[i + 1]);
+    for i in 0..n {
+        // A step back along the previous one overlaps it by more than their corner.
+        let prev = (i + n - 1) % n;
+        if (n > 1) & (dig_plan[i].n > 0) & (dig_plan[prev].n > 0) {
+            let back = matches!(
+                (dig_plan[prev].dir, dig_plan[i].dir),
+                (Direction::U, Direction::D)
+                    | (Direction::D, Direction::U)
+                    | (Direction::L, Direction::R)
+                    | (Direction::R, Direction::L)
+            );
+            if back {
+                return Err(PuzzleErr::SelfCrossing(
+                    cmp::min(prev, i),
+                    cmp::max(prev, i),
+                ));
+            }
+        }
+        for j in (i + 2)..n {
+            let adjacent = (i == 0) & (j == n - 1);
+            if !adjacent && segments_touch(segment(i), segment(j)) {
+                return Err(PuzzleErr::SelfCrossing(i, j));
+            }
+        }
+    }
+    Ok(vertices)
+}
+
 fn shoelace(vertices: &[(i64, i64)]) -> i64 {
     let a: i64 = zip(vertices.iter(), vertices[1..].iter())
         .map(|(a, b)| (a.0 * b.1) - (a.1 * b.0))
         .sum();
-    a / 2
+    a.abs() / 2
 }
 
 fn perimeter(vertices: &[(i64, i64)]) -> i64 {
     zip(vertices.iter(), vertices[1..].iter())
-        .map(|(a, b)| ((((a.0 - b.0).pow(2) + (a.1 - b.1).pow(2)) as f32).sqrt()) as i64)
+        .map(|(a, b)| a.0.abs_diff(b.0) as i64 + a.1.abs_diff(b.1) as i64)
         .sum()
 }
 
-pub fn puzzle_1(input: &str) -> Result<i64, PuzzleErr> {
-    let dig_plan = parse_input(input, &line_to_dig)?;
-    let vertices = dig_plan_to_vertices(&dig_plan);
+fn lagoon_size(dig_plan: &[Dig]) -> Result<i64, PuzzleErr> {
+    let vertices = validate_plan(dig_plan)?;
     Ok(shoelace(&vertices) + div_floor(perimeter(&vertices), 2) + 1)
 }
 
+pub fn puzzle_1(input: &str) -> Result<i64, PuzzleErr> {
+    lagoon_size(&parse_input(input, &line_to_dig)?)
+}
+
 pub fn puzzle_2(input: &str) -> Result<i64, PuzzleErr> {
-    let dig_plan = parse_input(input, &line_to_dig_2)?;
-    let vertices = dig_plan_to_vertices(&dig_plan);
-    Ok(shoelace(&vertices) + div_floor(perimeter(&vertices), 2) + 1)
+    lagoon_size(&parse_input(input, &line_to_dig_2)?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Text,
+    Ansi,
+    Svg,
+}
+
+// Largest lagoon (by bounding box) that will be drawn cell by cell.
+const MAX_RENDER_CELLS: i64 = 10_000_000;
+
+// The dug-out lagoon on a grid with the first row at the top: each trench cell with the
+// colour of the step that dug it, and the interior cells.
+struct Lagoon {
+    height: i64,
+    width: i64,
+    trench: HashMap<(i64, i64), Option<Rgb>>,
+    interior: HashSet<(i64, i64)>,
+}
+
+impl Lagoon {
+    fn new(dig_plan: &[Dig], vertices: &[(i64, i64)]) -> Result<Self, PuzzleErr> {
+        let max_up = vertices.iter().map(|v| v.0).max().unwrap();
+        let min_up = vertices.iter().map(|v| v.0).min().unwrap();
+        let min_right = vertices.iter().map(|v| v.1).min().unwrap();
+        let max_right = vertices.iter().map(|v| v.1).max().unwrap();
+        let (height, width) = (max_up - min_up + 1, max_right - min_right + 1);
+        if height * width > MAX_RENDER_CELLS {
+            return Err(PuzzleErr::TooLarge(height * width));
+        }
+        let to_cell = |v: &(i64, i64)| (max_up - v.0, v.1 - min_right);
+
+        let mut trench = HashMap::new();
+        for (dig, v) in zip(dig_plan.iter(), vertices.iter()) {
+            let (r, c) = to_cell(v);
+            let (dr, dc) = match dig.dir {
+                Direction::U => (-1, 0),
+                Direction::D => (1, 0),
+                Direction::L => (0, -1),
+                Direction::R => (0, 1),
+            };
+            for i in 1..=dig.n {
+                trench.insert((r + dr * i, c + dc * i), dig.color);
+            }
+        }
+
+        // Flood the ground outside the trench from a border around the grid.
+        let mut outside = HashSet::from([(-1, -1)]);
+        let mut queue = VecDeque::from([(-1, -1)]);
+        while let Some((r, c)) = queue.pop_front() {
+            for next in [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)] {
+                let in_bounds = (-1..=height).contains(&next.0) & (-1..=width).contains(&next.1);
+                if in_bounds && !trench.contains_key(&next) && outside.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        let interior = (0..height)
+            .flat_map(|r| (0..width).map(move |c| (r, c)))
+            .filter(|cell| !trench.contains_key(cell) & !outside.contains(cell))
+            .collect();
+        Ok(Self {
+            height,
+            width,
+            trench,
+            interior,
+        })
+    }
+
+    // Trench and interior cells are "#" (the trench in its colour with ANSI escapes) and
+    // the ground around the lagoon is ".".
+    fn render_text(&self, ansi: bool) -> String {
+        (0..self.height)
+            .map(|r| {
+                (0..self.width)
+                    .map(|c| match self.trench.get(&(r, c)) {
+                        Some(Some((red, green, blue))) if ansi => {
+                            format!("\x1b[38;2;{};{};{}m#\x1b[0m", red, green, blue)
+                        }
+                        Some(_) => "#".to_string(),
+                        None if self.interior.contains(&(r, c)) => "#".to_string(),
+                        None => ".".to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_svg(&self, dig_plan: &[Dig], vertices: &[(i64, i64)]) -> String {
+        let (height, width) = (self.height * SVG_TILE, self.width * SVG_TILE);
+        let max_up = vertices.iter().map(|v| v.0).max().unwrap();
+        let min_right = vertices.iter().map(|v| v.1).min().unwrap();
+        let center = |v: &(i64, i64)| {
+            (
+                (v.1 - min_right) * SVG_TILE + SVG_TILE / 2,
+                (max_up - v.0) * SVG_TILE + SVG_TILE / 2,
+            )
+        };
+
+        let mut lines = Vec::from_iter([format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        )]);
+        let points = vertices[1..]
+            .iter()
+            .map(|v| {
+                let (x, y) = center(v);
+                format!("{},{}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(format!(
+            r##"<polygon points="{}" fill="#3d3d3d" stroke="none"/>"##,
+            points
+        ));
+        for (dig, (a, b)) in zip(dig_plan.iter(), zip(vertices.iter(), vertices[1..].iter())) {
+            let ((x1, y1), (x2, y2)) = (center(a), center(b));
+            let (red, green, blue) = dig.color.unwrap_or((0, 0, 0));
+            lines.push(format!(
+                r##"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#{:02x}{:02x}{:02x}" stroke-width="{}" stroke-linecap="square"/>"##,
+                x1, y1, x2, y2, red, green, blue, SVG_TILE
+            ));
+        }
+        lines.push("</svg>".to_string());
+        lines.join("\n")
+    }
+}
+
+const SVG_TILE: i64 = 10;
+
+// Draw the part 1 lagoon with each trench step in the colour from its instruction.
+pub fn render(input: &str, format: RenderFormat) -> Result<String, PuzzleErr> {
+    let dig_plan = parse_input(input, &line_to_dig)?;
+    let vertices = validate_plan(&dig_plan)?;
+    let lagoon = Lagoon::new(&dig_plan, &vertices)?;
+    Ok(match format {
+        RenderFormat::Text => lagoon.render_text(false),
+        RenderFormat::Ansi => lagoon.render_text(true),
+        RenderFormat::Svg => lagoon.render_svg(&dig_plan, &vertices),
+    })
 }
 
 pub fn main(data_dir: &str) {
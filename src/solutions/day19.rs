@@ -1,8 +1,19 @@
 use crate::data::load;
+use crate::intervals::IntervalSet;
+use lazy_static::lazy_static;
 use regex::Regex;
-use std::{collections::HashMap, num::ParseIntError};
+use std::{collections::HashMap, num::ParseIntError, ops::Range};
 use thiserror::Error;
 
+lazy_static! {
+    static ref PART_RE: Regex =
+        Regex::new(r"x=(?<x>\d+),m=(?<m>\d+),a=(?<a>\d+),s=(?<s>\d+)").unwrap();
+    // a<2006:qkq
+    static ref RULE_RE: Regex =
+        Regex::new(r"(?<var_name>\w)(?<op_name>[<|>])(?<value>\d+):(?<res_name>\w+)").unwrap();
+    static ref WORKFLOW_RE: Regex = Regex::new(r"^(?<name>\w+)\{(?<rules>.+)\}").unwrap();
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleErr {
     #[error("Input parsing error: {}.", .0)]
@@ -11,6 +22,8 @@ pub enum PuzzleErr {
     ParseIntError(#[from] ParseIntError),
     #[error("Not yet implemented.")]
     NotImplemented,
+    #[error("Unknown workflow: {}.", .0)]
+    UnknownWorkflow(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,8 +37,7 @@ struct Part {
 impl TryFrom<&str> for Part {
     type Error = PuzzleErr;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new(r"x=(?<x>\d+),m=(?<m>\d+),a=(?<a>\d+),s=(?<s>\d+)").unwrap();
-        let Some(caps) = re.captures(value) else {
+        let Some(caps) = PART_RE.captures(value) else {
             return Err(PuzzleErr::ParseInputError(value.to_string()));
         };
         Ok(Self {
@@ -43,6 +55,51 @@ impl Part {
     }
 }
 
+// The ranges of ratings for each category of a part, i.e. a 4-D box of parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PartRanges {
+    x: IntervalSet<u64>,
+    m: IntervalSet<u64>,
+    a: IntervalSet<u64>,
+    s: IntervalSet<u64>,
+}
+
+impl PartRanges {
+    fn new(range: Range<u64>) -> Self {
+        let r = IntervalSet::from(range);
+        Self {
+            x: r.clone(),
+            m: r.clone(),
+            a: r.clone(),
+            s: r,
+        }
+    }
+
+    fn get(&self, var_name: &PartComponent) -> &IntervalSet<u64> {
+        match var_name {
+            PartComponent::X => &self.x,
+            PartComponent::M => &self.m,
+            PartComponent::A => &self.a,
+            PartComponent::S => &self.s,
+        }
+    }
+
+    fn with(&self, var_name: &PartComponent, values: IntervalSet<u64>) -> Self {
+        let mut new_ranges = self.clone();
+        match var_name {
+            PartComponent::X => new_ranges.x = values,
+            PartComponent::M => new_ranges.m = values,
+            PartComponent::A => new_ranges.a = values,
+            PartComponent::S => new_ranges.s = values,
+        };
+        new_ranges
+    }
+
+    fn volume(&self) -> u64 {
+        self.x.len() * self.m.len() * self.a.len() * self.s.len()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum RuleResult {
     A,
@@ -118,6 +175,22 @@ impl ComparisonOperation {
             CompOp::Less => part_val < self.value,
         }
     }
+
+    // Split the ranges into those that pass the comparison and those that do not.
+    fn split(&self, ranges: &PartRanges) -> (PartRanges, PartRanges) {
+        let values = ranges.get(&self.var_name);
+        let (pass, fail) = match self.op {
+            CompOp::Less => values.split_at(self.value as u64),
+            CompOp::Greater => {
+                let (fail, pass) = values.split_at(self.value as u64 + 1);
+                (pass, fail)
+            }
+        };
+        (
+            ranges.with(&self.var_name, pass),
+            ranges.with(&self.var_name, fail),
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -151,10 +224,7 @@ impl TryFrom<&str> for Rule {
             let res = RuleResult::from(value);
             return Ok(Rule::Simple(res));
         }
-        // a<2006:qkq
-        let re =
-            Regex::new(r"(?<var_name>\w)(?<op_name>[<|>])(?<value>\d+):(?<res_name>\w+)").unwrap();
-        let Some(caps) = re.captures(value) else {
+        let Some(caps) = RULE_RE.captures(value) else {
             return Err(PuzzleErr::ParseInputError(value.to_string()));
         };
         let op = ComparisonOperation {
@@ -176,8 +246,7 @@ struct Workflow {
 impl TryFrom<&str> for Workflow {
     type Error = PuzzleErr;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new(r"^(?<name>\w+)\{(?<rules>.+)\}").unwrap();
-        let Some(caps) = re.captures(value) else {
+        let Some(caps) = WORKFLOW_RE.captures(value) else {
             return Err(PuzzleErr::ParseInputError(value.to_string()));
         };
         let rules = &caps["rules"]
@@ -218,11 +287,17 @@ fn parse_parts(input: &str) -> Result<Vec<Part>, PuzzleErr> {
         .collect::<Result<Vec<Part>, PuzzleErr>>()
 }
 
+// The workflow and part rating blocks of the input.
+fn split_input(input: &str) -> Result<(&str, &str), PuzzleErr> {
+    input
+        .trim()
+        .split_once("\n\n")
+        .ok_or(PuzzleErr::ParseInputError(input.to_string()))
+}
+
 fn parse_input(input: &str) -> Result<(Vec<Workflow>, Vec<Part>), PuzzleErr> {
-    let split_input = input.trim().split("\n\n").collect::<Vec<_>>();
-    let workflows = parse_workflows(split_input[0])?;
-    let parts = parse_parts(split_input[1])?;
-    Ok((workflows, parts))
+    let (workflows, parts) = split_input(input)?;
+    Ok((parse_workflows(workflows)?, parse_parts(parts)?))
 }
 
 fn organize_part(part: &Part, workflows: &HashMap<&str, Workflow>) -> RuleResult {
@@ -259,13 +334,48 @@ pub fn puzzle_1(input: &str) -> Result<u32, PuzzleErr> {
     Ok(organize_parts(&parts, &workflows_map))
 }
 
-pub fn puzzle_2(input: &str) -> Result<u32, PuzzleErr> {
-    let (workflows, parts) = parse_input(input)?;
+fn count_accepted(
+    ranges: PartRanges,
+    workflow_name: &str,
+    workflows: &HashMap<&str, Workflow>,
+) -> Result<u64, PuzzleErr> {
+    let w = workflows
+        .get(workflow_name)
+        .ok_or(PuzzleErr::UnknownWorkflow(workflow_name.to_string()))?;
+    let mut remaining = ranges;
+    let mut n_accepted = 0;
+    for rule in w.rules.iter() {
+        if remaining.volume() == 0 {
+            break;
+        }
+        let (matched, res) = match rule {
+            Rule::Simple(res) => (remaining.clone(), res),
+            Rule::Comparison { op, res } => {
+                let (pass, fail) = op.split(&remaining);
+                remaining = fail;
+                (pass, res)
+            }
+        };
+        n_accepted += match res {
+            RuleResult::A => matched.volume(),
+            RuleResult::R => 0,
+            RuleResult::W(next_w) => count_accepted(matched, next_w, workflows)?,
+        };
+        if let Rule::Simple(_) = rule {
+            break;
+        }
+    }
+    Ok(n_accepted)
+}
+
+pub fn puzzle_2(input: &str) -> Result<u64, PuzzleErr> {
+    // Part ratings play no role in counting the accepted combinations.
+    let workflows = parse_workflows(split_input(input)?.0)?;
     let workflows_map = workflows
         .iter()
         .map(|w| (w.name.as_str(), w.clone()))
         .collect::<HashMap<&str, Workflow>>();
-    Ok(organize_parts(&parts, &workflows_map))
+    count_accepted(PartRanges::new(1..4001), "in", &workflows_map)
 }
 
 pub fn main(data_dir: &str) {
@@ -281,10 +391,10 @@ pub fn main(data_dir: &str) {
     assert_eq!(answer_1, Ok(509597));
 
     // Puzzle 2.
-    // let answer_2 = puzzle_2(&data);
-    // match answer_2 {
-    //     Ok(x) => println!(" Puzzle 2: {}", x),
-    //     Err(e) => panic!("No solution to puzzle 2: {}", e),
-    // }
-    // assert_eq!(answer_2, Ok(30449))
+    let answer_2 = puzzle_2(&data);
+    match answer_2 {
+        Ok(x) => println!(" Puzzle 2: {}", x),
+        Err(e) => panic!("No solution to puzzle 2: {}", e),
+    }
+    assert_eq!(answer_2, Ok(143219569011526))
 }
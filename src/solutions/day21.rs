@@ -1,21 +1,34 @@
 use crate::data::load;
-use std::{collections::HashSet, hash::Hash};
+use crate::math_utils::{MathError, PolynomialSequence};
+use crate::search;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleErr {
     #[error("Input parsing error.")]
     ParseInputError,
+    #[error("Extrapolation requires a square garden.")]
+    NotSquare,
+    #[error("Reachable plots did not settle into a quadratic within {} periods.", .0)]
+    NotQuadratic(usize),
+    #[error("A distance map over an infinite garden needs a step limit.")]
+    Unbounded,
+    #[error("Extrapolation error: {}", .0)]
+    Extrapolation(#[from] MathError),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos {
-    r: i32,
-    c: i32,
+pub struct Pos {
+    pub r: i32,
+    pub c: i32,
 }
 
 impl Pos {
-    fn new(r: i32, c: i32) -> Self {
+    pub fn new(r: i32, c: i32) -> Self {
         Self { r, c }
     }
 
@@ -27,54 +40,227 @@ impl Pos {
     }
 }
 
+// Whether the garden ends at its edges or repeats infinitely in every direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tiling {
+    Bounded,
+    Infinite,
+}
+
 struct GardenMap {
     start: Pos,
     garden_plots: HashSet<Pos>,
+    height: i32,
+    width: i32,
+    tiling: Tiling,
 }
 
 impl GardenMap {
+    fn is_garden_plot(&self, p: &Pos) -> bool {
+        match self.tiling {
+            Tiling::Bounded => self.garden_plots.contains(p),
+            Tiling::Infinite => self.garden_plots.contains(&Pos::new(
+                p.r.rem_euclid(self.height),
+                p.c.rem_euclid(self.width),
+            )),
+        }
+    }
+
     fn neighboring_garden_plots(&self, p: &Pos) -> HashSet<Pos> {
         [(-1, 0), (1, 0), (0, -1), (0, 1)]
             .iter()
             .map(|(dr, dc)| p.move_by(dr, dc))
-            .filter(|p| self.garden_plots.contains(p))
+            .filter(|p| self.is_garden_plot(p))
             .collect()
     }
+
+    fn distances(&self, max_steps: Option<usize>) -> HashMap<Pos, usize> {
+        search::bfs_distances(
+            [self.start],
+            |p| self.neighboring_garden_plots(p),
+            max_steps,
+        )
+    }
+
+    // Number of plots reachable in exactly `n` steps, for every `n` up to `max_steps`.
+    fn reachable_counts(&self, max_steps: usize) -> Vec<u64> {
+        let dists = self.distances(Some(max_steps));
+        let mut n_at = vec![0; max_steps + 1];
+        for d in dists.values() {
+            n_at[*d] += 1;
+        }
+        // A plot reachable in `d` steps can be revisited every other step afterwards.
+        let mut counts: Vec<u64> = Vec::with_capacity(max_steps + 1);
+        for (n, x) in n_at.iter().enumerate() {
+            counts.push(x + if n >= 2 { counts[n - 2] } else { 0 });
+        }
+        counts
+    }
 }
 
-fn parse_input(input: &str) -> GardenMap {
+fn parse_input(input: &str, tiling: Tiling) -> Result<GardenMap, PuzzleErr> {
     let mut start: Option<Pos> = None;
     let mut garden_plots = HashSet::new();
+    let (mut height, mut width) = (0, 0);
     for (r, line) in input.trim().lines().enumerate() {
         for (c, x) in line.trim().chars().enumerate() {
+            let pos = Pos::new(r as i32, c as i32);
             match x {
                 '.' => {
-                    garden_plots.insert(Pos::new(r as i32 + 1, c as i32 + 1));
+                    garden_plots.insert(pos);
                 }
                 'S' => {
-                    garden_plots.insert(Pos::new(r as i32 + 1, c as i32 + 1));
-                    start = Some(Pos::new(r as i32 + 1, c as i32 + 1));
+                    garden_plots.insert(pos);
+                    start = Some(pos);
                 }
-                _ => (),
+                '#' => (),
+                _ => return Err(PuzzleErr::ParseInputError),
             }
+            width = width.max(c as i32 + 1);
         }
+        height = r as i32 + 1;
     }
-    GardenMap {
-        start: start.unwrap(),
+    Ok(GardenMap {
+        start: start.ok_or(PuzzleErr::ParseInputError)?,
         garden_plots,
+        height,
+        width,
+        tiling,
+    })
+}
+
+// Number of garden plots reachable in exactly `n_steps` by breadth-first search.
+pub fn reachable_plots(input: &str, n_steps: usize, tiling: Tiling) -> Result<u64, PuzzleErr> {
+    let map = parse_input(input, tiling)?;
+    Ok(map.reachable_counts(n_steps)[n_steps])
+}
+
+// Number of garden plots reachable in exactly `n` steps, for every `n` in `0..=max_steps`.
+pub fn reachable_series(
+    input: &str,
+    max_steps: usize,
+    tiling: Tiling,
+) -> Result<Vec<u64>, PuzzleErr> {
+    Ok(parse_input(input, tiling)?.reachable_counts(max_steps))
+}
+
+// Fewest steps from the start to every reachable garden plot. With an infinite tiling,
+// `max_steps` is required to keep the search finite.
+pub fn distance_map(
+    input: &str,
+    max_steps: Option<usize>,
+    tiling: Tiling,
+) -> Result<HashMap<Pos, usize>, PuzzleErr> {
+    if tiling == Tiling::Infinite && max_steps.is_none() {
+        return Err(PuzzleErr::Unbounded);
     }
+    Ok(parse_input(input, tiling)?.distances(max_steps))
 }
 
-pub fn puzzle_1(input: &str, n_steps: u32) -> Result<usize, PuzzleErr> {
-    let map = parse_input(input);
-    let mut current_positions: HashSet<Pos> = HashSet::from_iter([map.start]);
-    for _ in 0..n_steps {
-        current_positions = current_positions
-            .iter()
-            .flat_map(|p| map.neighboring_garden_plots(p))
-            .collect();
+// Where a reachable-plots series starts growing with a fixed pattern every `period` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrowthSettling {
+    pub period: usize,
+    // First step from which the series, sampled every `period` steps, is quadratic.
+    pub step: usize,
+    // The quadratic followed by the samples taken at steps `n` with `n % period == i`,
+    // at index `i`, counted in periods from the first such step at or after `step`.
+    quadratics: Vec<PolynomialSequence<i64>>,
+}
+
+impl GrowthSettling {
+    // First step at or after `step` with the same residue as `n_steps`.
+    fn anchor(&self, n_steps: u64) -> u64 {
+        let (p, step) = (self.period as u64, self.step as u64);
+        step + (n_steps % p + p - step % p) % p
     }
-    Ok(current_positions.len())
+
+    // Extend the settled quadratic growth to any later step.
+    pub fn extrapolate(&self, series: &[u64], n_steps: u64) -> Result<u64, PuzzleErr> {
+        if n_steps < series.len() as u64 {
+            return Ok(series[n_steps as usize]);
+        }
+        let p = self.period as u64;
+        let anchor = self.anchor(n_steps);
+        let quadratic = &self.quadratics[(n_steps % p) as usize];
+        Ok(quadratic.value_at(((n_steps - anchor) / p) as i64)? as u64)
+    }
+}
+
+// Samples `series[n], series[n + period], ...`, as many as `n_samples`.
+fn samples(series: &[u64], n: usize, period: usize, n_samples: usize) -> Vec<i64> {
+    (0..n_samples)
+        .map(|k| series[n + k * period] as i64)
+        .collect()
+}
+
+// Find the earliest step after which every sample `series[n], series[n + period], ...`
+// fits a quadratic for the rest of the series. At least one full period of steps must
+// confirm the pattern.
+pub fn growth_settling(series: &[u64], period: usize) -> Option<GrowthSettling> {
+    let quadratic = |n: usize| {
+        PolynomialSequence::fit(&samples(series, n, period, 4))
+            .ok()
+            .filter(|q| q.degree() <= 2)
+    };
+    let last = series.len().checked_sub(3 * period + 1)?;
+    let step = (0..=last)
+        .rev()
+        .take_while(|n| quadratic(*n).is_some())
+        .last()?;
+    if last + 1 - step < period {
+        return None;
+    }
+    let mut settling = GrowthSettling {
+        period,
+        step,
+        quadratics: Vec::new(),
+    };
+    settling.quadratics = (0..period)
+        .map(|i| quadratic(settling.anchor(i as u64) as usize))
+        .collect::<Option<_>>()?;
+    Some(settling)
+}
+
+pub fn puzzle_1(input: &str, n_steps: u32) -> Result<u64, PuzzleErr> {
+    reachable_plots(input, n_steps as usize, Tiling::Bounded)
+}
+
+// Maximum number of garden periods to simulate while waiting for quadratic growth.
+const MAX_PERIODS: usize = 40;
+
+// Once the reachable region spans several copies of the garden, it grows as a diamond
+// that gains whole tiles each period. Sampling every `n_steps % size + k * size` steps
+// then gives a quadratic in `k`, which is found from the first window of samples with
+// constant second differences and extrapolated to `n_steps`.
+pub fn puzzle_2(input: &str, n_steps: u64) -> Result<u64, PuzzleErr> {
+    let map = parse_input(input, Tiling::Infinite)?;
+    if map.height != map.width {
+        return Err(PuzzleErr::NotSquare);
+    }
+    let size = map.height as u64;
+    let (offset, target) = (n_steps % size, n_steps / size);
+    // Simulate a few periods at a time, doubling until a quadratic window is found.
+    let mut n_periods = 5;
+    while n_periods <= MAX_PERIODS {
+        let n_sampled = n_periods.min(target as usize + 1);
+        let counts = map.reachable_counts((offset + (n_sampled as u64 - 1) * size) as usize);
+        let samples = (0..n_sampled)
+            .map(|k| counts[(offset + k as u64 * size) as usize] as i64)
+            .collect::<Vec<_>>();
+        if target < n_sampled as u64 {
+            return Ok(samples[target as usize] as u64);
+        }
+        for (k, window) in samples.windows(5).enumerate() {
+            if let Ok(seq) = PolynomialSequence::fit(window) {
+                if seq.degree() <= 2 {
+                    return Ok(seq.value_at((target - k as u64) as i64)? as u64);
+                }
+            }
+        }
+        n_periods *= 2;
+    }
+    Err(PuzzleErr::NotQuadratic(MAX_PERIODS))
 }
 
 pub fn main(data_dir: &str) {
@@ -87,13 +273,13 @@ pub fn main(data_dir: &str) {
         Ok(x) => println!(" Puzzle 1: {}", x),
         Err(e) => panic!("No solution to puzzle 1: {}.", e),
     }
-    // assert_eq!(answer_1, Ok(37113));
+    assert_eq!(answer_1, Ok(3637));
 
     // Puzzle 2.
-    // let answer_2 = puzzle_2(&data);
-    // match answer_2 {
-    //     Ok(x) => println!(" Puzzle 2: {}", x),
-    //     Err(e) => panic!("No solution to puzzle 2: {}", e),
-    // }
-    // assert_eq!(answer_2, Ok(30449))
+    let answer_2 = puzzle_2(&data, 26501365);
+    match answer_2 {
+        Ok(x) => println!(" Puzzle 2: {}", x),
+        Err(e) => panic!("No solution to puzzle 2: {}", e),
+    }
+    assert_eq!(answer_2, Ok(601113643448699))
 }
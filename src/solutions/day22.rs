@@ -1,44 +1,168 @@
 use crate::data::load;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PuzzleErr {
+    #[error("Input parsing error: {}.", .0)]
+    ParseInputError(String),
+    #[error("Integer parsing error.")]
+    ParseIntError(#[from] std::num::ParseIntError),
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Pos {
-    x: u32,
-    y: u32,
-    z: u32,
+pub struct Pos {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Brick {
-    a: Pos,
-    b: Pos,
-}
-
-fn parse_line(line: &str) -> Brick {
-    let poss: Vec<Pos> = line
-        .split('~')
-        .map(|s| {
-            let ints: Vec<u32> = s.split(',').map(|a| a.parse::<u32>().unwrap()).collect();
-            Pos {
-                x: ints[0],
-                y: ints[1],
-                z: ints[2],
-            }
-        })
-        .collect();
-    Brick {
-        a: poss[0],
-        b: poss[1],
+pub struct Brick {
+    pub a: Pos,
+    pub b: Pos,
+}
+
+impl Brick {
+    fn bottom(&self) -> u32 {
+        cmp::min(self.a.z, self.b.z)
+    }
+
+    fn height(&self) -> u32 {
+        self.a.z.abs_diff(self.b.z) + 1
+    }
+
+    // The (x, y) cells covered by the brick's footprint.
+    fn footprint(&self) -> Vec<(u32, u32)> {
+        let xs = cmp::min(self.a.x, self.b.x)..=cmp::max(self.a.x, self.b.x);
+        let ys = cmp::min(self.a.y, self.b.y)..=cmp::max(self.a.y, self.b.y);
+        xs.flat_map(|x| ys.clone().map(move |y| (x, y))).collect()
+    }
+
+    fn drop_to(&self, z: u32) -> Self {
+        let dz = self.bottom() - z;
+        let mut brick = *self;
+        brick.a.z -= dz;
+        brick.b.z -= dz;
+        brick
+    }
+}
+
+fn parse_pos(s: &str) -> Result<Pos, PuzzleErr> {
+    let ints = s
+        .split(',')
+        .map(|a| a.trim().parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    match ints[..] {
+        [x, y, z] => Ok(Pos { x, y, z }),
+        _ => Err(PuzzleErr::ParseInputError(s.to_string())),
     }
 }
 
-fn parse_input(input: &str) -> Vec<Brick> {
+fn parse_line(line: &str) -> Result<Brick, PuzzleErr> {
+    let Some((a, b)) = line.trim().split_once('~') else {
+        return Err(PuzzleErr::ParseInputError(line.to_string()));
+    };
+    Ok(Brick {
+        a: parse_pos(a)?,
+        b: parse_pos(b)?,
+    })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Brick>, PuzzleErr> {
     input.trim().lines().map(parse_line).collect()
 }
 
-pub fn puzzle_1(input: &str) -> u32 {
-    let bricks = parse_input(input);
-    bricks.iter().for_each(|b| println!("{:?}", b));
-    0
+// The settled bricks and which bricks rest directly on which.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportGraph {
+    // Settled bricks, in the order they were dropped.
+    pub bricks: Vec<Brick>,
+    // `supports[i]` are the bricks resting directly on brick `i`.
+    pub supports: Vec<Vec<usize>>,
+    // `supported_by[i]` are the bricks that brick `i` rests directly on.
+    pub supported_by: Vec<Vec<usize>>,
+}
+
+impl SupportGraph {
+    // Whether brick `i` can be removed without any other brick falling.
+    pub fn can_disintegrate(&self, i: usize) -> bool {
+        self.supports[i]
+            .iter()
+            .all(|j| self.supported_by[*j].len() > 1)
+    }
+
+    // Number of other bricks that would fall if brick `i` were removed.
+    pub fn chain_reaction(&self, i: usize) -> usize {
+        let mut fallen = HashSet::from([i]);
+        // A brick is always dropped after the bricks it rests on, so those are decided first.
+        for j in (i + 1)..self.bricks.len() {
+            let below = &self.supported_by[j];
+            if !below.is_empty() & below.iter().all(|k| fallen.contains(k)) {
+                fallen.insert(j);
+            }
+        }
+        fallen.len() - 1
+    }
+}
+
+// Drop the bricks in order of height onto a height map of the tallest brick in each
+// (x, y) column, recording the bricks each one comes to rest on.
+fn settle(mut bricks: Vec<Brick>) -> SupportGraph {
+    bricks.sort_by_key(|b| b.bottom());
+    let mut height_map = HashMap::<(u32, u32), (u32, usize)>::new();
+    let mut settled = Vec::new();
+    let mut supports = vec![Vec::new(); bricks.len()];
+    let mut supported_by = vec![Vec::new(); bricks.len()];
+    for (i, brick) in bricks.iter().enumerate() {
+        let footprint = brick.footprint();
+        let below = footprint
+            .iter()
+            .filter_map(|xy| height_map.get(xy))
+            .collect::<Vec<_>>();
+        let rest_z = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+        let mut resting_on = below
+            .iter()
+            .filter(|(z, _)| *z == rest_z)
+            .map(|(_, j)| *j)
+            .collect::<Vec<_>>();
+        resting_on.sort();
+        resting_on.dedup();
+        for j in resting_on.iter() {
+            supports[*j].push(i);
+        }
+        supported_by[i] = resting_on;
+
+        let top = rest_z + brick.height();
+        for xy in footprint {
+            height_map.insert(xy, (top, i));
+        }
+        settled.push(brick.drop_to(rest_z + 1));
+    }
+    SupportGraph {
+        bricks: settled,
+        supports,
+        supported_by,
+    }
+}
+
+pub fn support_graph(input: &str) -> Result<SupportGraph, PuzzleErr> {
+    Ok(settle(parse_input(input)?))
+}
+
+pub fn puzzle_1(input: &str) -> Result<usize, PuzzleErr> {
+    let graph = support_graph(input)?;
+    Ok((0..graph.bricks.len())
+        .filter(|i| graph.can_disintegrate(*i))
+        .count())
+}
+
+pub fn puzzle_2(input: &str) -> Result<usize, PuzzleErr> {
+    let graph = support_graph(input)?;
+    Ok((0..graph.bricks.len())
+        .map(|i| graph.chain_reaction(i))
+        .sum())
 }
 
 pub fn main(data_dir: &str) {
@@ -47,15 +171,17 @@ pub fn main(data_dir: &str) {
 
     // Puzzle 1.
     let answer_1 = puzzle_1(&data);
-    println!(" Puzzle 1: {}", answer_1)
-
-    // assert_eq!(answer_1, Ok(37113));
+    match answer_1 {
+        Ok(x) => println!(" Puzzle 1: {}", x),
+        Err(e) => panic!("No solution to puzzle 1: {}.", e),
+    }
+    assert_eq!(answer_1, Ok(461));
 
     // Puzzle 2.
-    // let answer_2 = puzzle_2(&data);
-    // match answer_2 {
-    //     Ok(x) => println!(" Puzzle 2: {}", x),
-    //     Err(e) => panic!("No solution to puzzle 2: {}", e),
-    // }
-    // assert_eq!(answer_2, Ok(30449))
+    let answer_2 = puzzle_2(&data);
+    match answer_2 {
+        Ok(x) => println!(" Puzzle 2: {}", x),
+        Err(e) => panic!("No solution to puzzle 2: {}", e),
+    }
+    assert_eq!(answer_2, Ok(74074))
 }
@@ -1,4 +1,4 @@
-use aoc_2023::solutions::day05::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day05::{puzzle_1, puzzle_2, PuzzleErr};
 
 const EXAMPLE_INPUT: &str = "
 seeds: 79 14 55 13
@@ -45,3 +45,29 @@ fn example_1_puzzle_1() {
 fn example_1_puzzle_2() {
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT), Ok(46));
 }
+
+#[test]
+fn puzzle_2_other_seed_ranges() {
+    let maps = self::EXAMPLE_INPUT.split_once("\n\n").unwrap().1;
+    for (seeds, lowest) in [
+        ("79 14 55 13 0 10", 22),
+        ("98 2", 19),
+        ("0 100", 0),
+        ("14 1 90 5", 43),
+    ] {
+        let input = format!("seeds: {}\n\n{}", seeds, maps);
+        assert_eq!(puzzle_2(&input), Ok(lowest), "seeds: {}", seeds);
+    }
+}
+
+#[test]
+fn puzzle_2_odd_seed_count() {
+    let input = self::EXAMPLE_INPUT.replace("seeds: 79 14 55 13", "seeds: 79 14 55");
+    assert_eq!(puzzle_2(&input), Err(PuzzleErr::OddSeedCount(3)));
+}
+
+#[test]
+fn empty_seed_ranges() {
+    let input = self::EXAMPLE_INPUT.replace("seeds: 79 14 55 13", "seeds: 79 0 55 0");
+    assert_eq!(puzzle_2(&input), Err(PuzzleErr::NoSeeds));
+}
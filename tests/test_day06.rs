@@ -1,4 +1,4 @@
-use aoc_2023::solutions::day06::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day06::{puzzle_1, puzzle_2, Race, WinningHolds};
 
 const EXAMPLE_INPUT: &str = "
 Time:      7  15   30
@@ -14,3 +14,108 @@ fn example_1_puzzle_1() {
 fn example_1_puzzle_2() {
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT), Ok(71503));
 }
+
+#[test]
+fn example_1_winning_holds() {
+    let holds = [(7, 9), (15, 40), (30, 200)]
+        .iter()
+        .map(|(t, d)| Race::new(*t, *d).winning_holds())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        holds,
+        vec![
+            Some(WinningHolds { first: 2, last: 5 }),
+            Some(WinningHolds { first: 4, last: 11 }),
+            Some(WinningHolds {
+                first: 11,
+                last: 19
+            }),
+        ]
+    );
+    assert_eq!(
+        Race::new(71530, 940200).winning_holds(),
+        Some(WinningHolds {
+            first: 14,
+            last: 71516
+        })
+    );
+}
+
+#[test]
+fn matches_brute_force() {
+    for t in 0..60u128 {
+        for d in 0..(t * t / 4 + 3) {
+            let winners = (0..=t).filter(|h| h * (t - h) > d).collect::<Vec<_>>();
+            let expected = winners.first().map(|first| WinningHolds {
+                first: *first,
+                last: *winners.last().unwrap(),
+            });
+            let race = Race::new(t, d);
+            assert_eq!(race.winning_holds(), expected, "race {:?}", race);
+            assert_eq!(race.n_ways_to_win(), winners.len() as u128);
+        }
+    }
+}
+
+#[test]
+fn no_winning_holds() {
+    // Only ties with the record.
+    assert_eq!(Race::new(4, 4).winning_holds(), None);
+    assert_eq!(Race::new(3, 2).winning_holds(), None);
+    assert_eq!(Race::new(10, u128::MAX).n_ways_to_win(), 0);
+}
+
+#[test]
+fn long_races() {
+    let t = 10u128.pow(19);
+    assert_eq!(Race::new(t, 0).n_ways_to_win(), t - 1);
+    // The record is one short of the best possible distance.
+    let t = 1u128 << 63;
+    let best = (t / 2) * (t / 2);
+    assert_eq!(
+        Race::new(t, best - 1).winning_holds(),
+        Some(WinningHolds {
+            first: t / 2,
+            last: t / 2
+        })
+    );
+    assert_eq!(Race::new(t, best).winning_holds(), None);
+}
+
+// Checks `first` and `last` against the record, treating distances too large for a
+// u128 as wins.
+fn assert_boundaries(race: Race, holds: WinningHolds) {
+    let beats = |hold: u128| {
+        hold.checked_mul(race.duration - hold)
+            .is_none_or(|d| d > race.record_distance)
+    };
+    assert!(beats(holds.first) && !beats(holds.first - 1), "{:?}", race);
+    assert!(beats(holds.last) && !beats(holds.last + 1), "{:?}", race);
+}
+
+#[test]
+fn races_longer_than_u64() {
+    let race = Race::new(u128::MAX, 0);
+    assert_eq!(race.n_ways_to_win(), u128::MAX - 1);
+    let race = Race::new(u128::MAX, u128::MAX);
+    assert_eq!(
+        race.winning_holds(),
+        Some(WinningHolds {
+            first: 2,
+            last: u128::MAX - 2
+        })
+    );
+    for (t, d) in [
+        (u128::MAX - 1, u128::MAX),
+        (1 << 100, 1 << 127),
+        ((1 << 65) + 1, u128::MAX / 3),
+        (1 << 65, u128::MAX),
+        (1 << 64, u128::MAX / 4),
+    ] {
+        let race = Race::new(t, d);
+        assert_boundaries(race, race.winning_holds().unwrap());
+    }
+    // The longest race with a record equal to its best distance.
+    let h = 1u128 << 63;
+    assert_eq!(Race::new(2 * h + 1, h * (h + 1)).winning_holds(), None);
+}
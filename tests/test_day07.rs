@@ -1,4 +1,6 @@
-use aoc_2023::solutions::day07::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day07::{
+    parse_input, puzzle_1, puzzle_2, HandType, PuzzleErr, Ruleset, TieBreak,
+};
 
 const EXAMPLE_INPUT: &str = "
 32T3K 765
@@ -17,3 +19,109 @@ fn example_1_puzzle_1() {
 fn example_1_puzzle_2() {
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT), Ok(5905));
 }
+
+#[test]
+fn custom_rulesets() {
+    let hands = parse_input(self::EXAMPLE_INPUT).unwrap();
+    let ace_low = Ruleset::new("A23456789TJQK", None, 5, TieBreak::InOrder).unwrap();
+    assert_eq!(ace_low.total_winnings(&hands), Ok(6440));
+    let threes_wild = Ruleset::new("23456789TJQKA", Some('3'), 5, TieBreak::InOrder).unwrap();
+    assert_eq!(threes_wild.total_winnings(&hands), Ok(7722));
+    assert_eq!(threes_wild.hand_type(&hands[0]), HandType::ThreeKind);
+
+    let hands = parse_input("2A345 1\n3KQJT 2").unwrap();
+    assert_eq!(Ruleset::standard().total_winnings(&hands), Ok(5));
+    let sorted = Ruleset::new("23456789TJQKA", None, 5, TieBreak::Sorted).unwrap();
+    assert_eq!(sorted.total_winnings(&hands), Ok(4));
+}
+
+#[test]
+fn other_hand_sizes() {
+    let hands = parse_input("AAK 10\nKKK 5\n23A 7\nJ2J 3\nQ2K 1").unwrap();
+    let threes = Ruleset::new("J23456789TQKA", Some('J'), 3, TieBreak::InOrder).unwrap();
+    assert_eq!(threes.total_winnings(&hands), Ok(76));
+    assert_eq!(threes.hand_type(&hands[3]), HandType::ThreeKind);
+
+    let hands = parse_input("JJJJJJ 1\n222233 2").unwrap();
+    let sixes = Ruleset::new("J23456789TQKA", Some('J'), 6, TieBreak::InOrder).unwrap();
+    assert_eq!(sixes.hand_type(&hands[0]), HandType::FiveKind);
+    assert_eq!(sixes.hand_type(&hands[1]), HandType::FourKind);
+}
+
+#[test]
+fn invalid_hands_and_rules() {
+    let hands = parse_input("32T3X 765").unwrap();
+    assert_eq!(
+        Ruleset::standard().total_winnings(&hands),
+        Err(PuzzleErr::UnknownCard('X'))
+    );
+    let hands = parse_input("32T3 765").unwrap();
+    assert_eq!(
+        Ruleset::standard().total_winnings(&hands),
+        Err(PuzzleErr::WrongHandSize(4, 5))
+    );
+    assert_eq!(
+        puzzle_1("32T3K"),
+        Err(PuzzleErr::InputParsingError("32T3K"))
+    );
+    assert!(matches!(
+        Ruleset::new("23J4J", None, 5, TieBreak::InOrder),
+        Err(PuzzleErr::InvalidRuleset(_))
+    ));
+    assert!(matches!(
+        Ruleset::new("2345", Some('J'), 5, TieBreak::InOrder),
+        Err(PuzzleErr::InvalidRuleset(_))
+    ));
+    assert!(matches!(
+        Ruleset::new("2345", None, 0, TieBreak::InOrder),
+        Err(PuzzleErr::InvalidRuleset(_))
+    ));
+}
+
+#[test]
+fn hand_reports() {
+    let hands = parse_input(self::EXAMPLE_INPUT).unwrap();
+    let reports = Ruleset::jokers().report(&hands).unwrap();
+    let summary = reports
+        .iter()
+        .map(|r| {
+            (
+                r.hand.to_string(),
+                r.hand_type,
+                r.rank,
+                r.winnings,
+                r.substitution,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("32T3K".to_string(), HandType::OnePair, 1, 765, None),
+            ("KK677".to_string(), HandType::TwoPair, 2, 56, None),
+            ("T55J5".to_string(), HandType::FourKind, 3, 2052, Some('5')),
+            ("QQQJA".to_string(), HandType::FourKind, 4, 1932, Some('Q')),
+            ("KTJJT".to_string(), HandType::FourKind, 5, 1100, Some('T')),
+        ]
+    );
+    assert_eq!(
+        reports[2].to_string(),
+        "    3  T55J5  FourKind      684        2052  (wild as 5)"
+    );
+    assert!(Ruleset::standard()
+        .report(&hands)
+        .unwrap()
+        .iter()
+        .all(|r| r.substitution.is_none()));
+}
+
+#[test]
+fn wild_substitutions() {
+    let rules = Ruleset::jokers();
+    let hands = parse_input("JJJJJ 1\nJ2K2K 1\nJ2345 1").unwrap();
+    assert_eq!(rules.substitution(&hands[0]), Some('A'));
+    assert_eq!(rules.substitution(&hands[1]), Some('K'));
+    assert_eq!(rules.hand_type(&hands[1]), HandType::FullHouse);
+    assert_eq!(rules.substitution(&hands[2]), Some('5'));
+    assert_eq!(rules.hand_type(&hands[2]), HandType::OnePair);
+}
@@ -1,4 +1,8 @@
-use aoc_2023::solutions::day10::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day10::{
+    enclosed_tiles, main_loop, puzzle_1, puzzle_2, render, write_render, Coord, PuzzleErr,
+    RenderFormat,
+};
+use std::collections::HashSet;
 
 const EXAMPLE_INPUT_1: &str = "
 .....
@@ -14,6 +18,39 @@ fn puzzle_1_example_1() {
     assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1), Ok(4));
 }
 
+#[test]
+fn main_loop_example_1() {
+    let _ = env_logger::try_init();
+    let expected = [
+        (1, 1),
+        (2, 1),
+        (3, 1),
+        (3, 2),
+        (3, 3),
+        (2, 3),
+        (1, 3),
+        (1, 2),
+    ]
+    .iter()
+    .map(|(r, c)| Coord { r: *r, c: *c })
+    .collect::<Vec<_>>();
+    assert_eq!(main_loop(self::EXAMPLE_INPUT_1), Ok(expected));
+}
+
+#[test]
+fn main_loop_errors() {
+    let _ = env_logger::try_init();
+    assert_eq!(main_loop(".|.\n.-."), Err(PuzzleErr::MissingStart));
+    assert_eq!(
+        main_loop(".|.\n-S-\n.|."),
+        Err(PuzzleErr::AmbiguousStart(Coord { r: 1, c: 1 }))
+    );
+    assert_eq!(
+        main_loop(".....\n.S-7.\n.|.|.\n.L-..\n....."),
+        Err(PuzzleErr::BrokenLoop(Coord { r: 3, c: 3 }))
+    );
+}
+
 const EXAMPLE_INPUT_2: &str = "
 ..F7.
 .FJ|.
@@ -28,11 +65,12 @@ fn puzzle_1_example_2() {
     assert_eq!(puzzle_1(self::EXAMPLE_INPUT_2), Ok(8));
 }
 
-// #[test]
-// fn example_1_puzzle_2() {
-//     let _ = env_logger::try_init();
-//     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(2));
-// }
+#[test]
+fn puzzle_2_example_0() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(1));
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_2), Ok(1));
+}
 
 const EXAMPLE_INPUT_3: &str = "
 ...........
@@ -64,6 +102,58 @@ const EXAMPLE_INPUT_4: &str = "
 ..........
 ";
 
+#[test]
+fn puzzle_2_example_1_tiles() {
+    let _ = env_logger::try_init();
+    let expected = [(6, 2), (6, 3), (6, 7), (6, 8)]
+        .iter()
+        .map(|(r, c)| Coord { r: *r, c: *c })
+        .collect::<HashSet<_>>();
+    assert_eq!(enclosed_tiles(self::EXAMPLE_INPUT_3), Ok(expected));
+}
+
+const EXAMPLE_3_RENDERED: &str = "
+OOOOOOOOOOO
+O┌───────┐O
+O│┌─────┐│O
+O││OOOOO││O
+O││OOOOO││O
+O│└─┐O┌─┘│O
+O│II│O│II│O
+O└──┘O└──┘O
+OOOOOOOOOOO
+";
+
+#[test]
+fn render_example_3() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        render(self::EXAMPLE_INPUT_3, RenderFormat::Text),
+        Ok(self::EXAMPLE_3_RENDERED.trim().to_string())
+    );
+    let ansi = render(self::EXAMPLE_INPUT_3, RenderFormat::Ansi).unwrap();
+    assert_eq!(ansi.matches("\x1b[32mI").count(), 4);
+    let svg = render(self::EXAMPLE_INPUT_3, RenderFormat::Svg).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches(r##"fill="#6cc070""##).count(), 4);
+    assert_eq!(svg.matches("<polygon").count(), 1);
+}
+
+#[test]
+fn write_render_example_3() {
+    let _ = env_logger::try_init();
+    let path = std::env::temp_dir().join("aoc_2023_day10_example_3.svg");
+    let path = path.to_str().unwrap();
+    assert_eq!(
+        write_render(self::EXAMPLE_INPUT_3, RenderFormat::Svg, path),
+        Ok(())
+    );
+    assert_eq!(
+        std::fs::read_to_string(path).ok(),
+        render(self::EXAMPLE_INPUT_3, RenderFormat::Svg).ok()
+    );
+}
+
 #[test]
 fn puzzle_2_example_2() {
     let _ = env_logger::try_init();
@@ -89,21 +179,32 @@ fn puzzle_2_example_3() {
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_5), Ok(8));
 }
 
-// const EXAMPLE_INPUT_6: &str = "
-// FF7FSF7F7F7F7F7F---7
-// L|LJ||||||||||||F--J
-// FL-7LJLJ||||||LJL-77
-// F--JF--7||LJLJ7F7FJ-
-// L---JF-JLJ.||-FJLJJ7
-// |F|F-JF---7F7-L7L|7|
-// |FFJF7L7F-JF7|JL---7
-// 7-L-JL7||F7|L7F-7F7|
-// L.L7LFJ|||||FJL7||LJ
-// L7JLJL-JLJLJL--JLJ.L
-// ";
-
-// #[test]
-// fn puzzle_2_example_4() {
-//     let _ = env_logger::try_init();
-//     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_6), Ok(10));
-// }
+const EXAMPLE_INPUT_6: &str = "
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+#[test]
+fn puzzle_2_example_4() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_6), Ok(10));
+}
+
+#[test]
+fn render_ragged_rows() {
+    let _ = env_logger::try_init();
+    let input = "S-7\n|.|\nL-J\n..";
+    assert_eq!(
+        render(input, RenderFormat::Text),
+        Ok("┌─┐\n│I│\n└─┘\nOO ".to_string())
+    );
+    assert_eq!(puzzle_2(input), Ok(1));
+}
@@ -1,4 +1,6 @@
-use aoc_2023::solutions::day11::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day11::{
+    expanded_galaxies, nearest_pairs, puzzle_1, puzzle_2, sum_of_distances, Coord,
+};
 
 const EXAMPLE_INPUT_1: &str = "
 ...#......
@@ -116,3 +118,45 @@ fn puzzle_2_example_6() {
         puzzle_2(self::EX1_EXPECTED_OUT_X10, 0),
     );
 }
+
+#[test]
+fn per_axis_expansion() {
+    let _ = env_logger::try_init();
+    assert_eq!(sum_of_distances(self::EXAMPLE_INPUT_1, 1, 1), 292);
+    assert_eq!(sum_of_distances(self::EXAMPLE_INPUT_1, 10, 1), 616);
+    assert_eq!(sum_of_distances(self::EXAMPLE_INPUT_1, 1, 3), 384);
+    assert_eq!(sum_of_distances(self::EXAMPLE_INPUT_1, 2, 2), 374);
+    let galaxies = expanded_galaxies(self::EXAMPLE_INPUT_1, 10, 1);
+    assert_eq!(galaxies[0], Coord { r: 0, c: 3 });
+    assert_eq!(galaxies[4], Coord { r: 14, c: 1 });
+    // Empty rows and columns before the first galaxy expand too.
+    assert_eq!(
+        expanded_galaxies("...\n.#.", 10, 1),
+        vec![Coord { r: 10, c: 1 }]
+    );
+    assert_eq!(
+        expanded_galaxies("...\n.#.", 10, 3),
+        vec![Coord { r: 10, c: 3 }]
+    );
+}
+
+#[test]
+fn huge_expansion() {
+    let _ = env_logger::try_init();
+    let factor = 10u128.pow(30);
+    assert_eq!(
+        sum_of_distances(self::EXAMPLE_INPUT_1, factor, factor),
+        292 + 82 * (factor - 1)
+    );
+}
+
+#[test]
+fn nearest_pairs_example_1() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        nearest_pairs(self::EXAMPLE_INPUT_1, 2, 2, 4),
+        vec![(5, 1, 3), (5, 2, 4), (5, 6, 8), (5, 7, 8)]
+    );
+    assert_eq!(nearest_pairs(self::EXAMPLE_INPUT_1, 2, 2, 100).len(), 36);
+    assert_eq!(nearest_pairs(self::EXAMPLE_INPUT_1, 2, 2, 0), vec![]);
+}
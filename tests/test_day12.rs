@@ -1,4 +1,6 @@
-use aoc_2023::solutions::day12::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day12::{
+    count_all_arrangements, parse_and_expand_input, puzzle_1, puzzle_2, Condition,
+};
 
 const EXAMPLE_INPUT_1: &str = "
 ???.### 1,1,3
@@ -20,3 +22,95 @@ fn puzzle_2_example_1() {
     let _ = env_logger::try_init();
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(525152));
 }
+
+#[test]
+fn count_arrangements_per_row() {
+    let _ = env_logger::try_init();
+    let rows = parse_and_expand_input(self::EXAMPLE_INPUT_1, 1).unwrap();
+    assert_eq!(
+        rows.iter()
+            .map(|r| r.count_arrangements())
+            .collect::<Vec<_>>(),
+        vec![1, 4, 1, 1, 4, 10]
+    );
+    let rows = parse_and_expand_input(self::EXAMPLE_INPUT_1, 5).unwrap();
+    assert_eq!(
+        rows.iter()
+            .map(|r| r.count_arrangements())
+            .collect::<Vec<_>>(),
+        vec![1, 16384, 1, 16, 2500, 506250]
+    );
+}
+
+#[test]
+fn count_arrangements_beyond_u64() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        count_all_arrangements("???????? 1,2", 20),
+        Ok(114556848244965165743109806892471)
+    );
+}
+
+#[test]
+fn unfold_row() {
+    let _ = env_logger::try_init();
+    let row = &parse_and_expand_input(".# 1", 5).unwrap()[0];
+    assert_eq!(format!("{}", row), ".#?.#?.#?.#?.#  --  1, 1, 1, 1, 1");
+}
+
+#[test]
+fn enumerate_arrangements() {
+    let _ = env_logger::try_init();
+    let row = &parse_and_expand_input("?###???????? 3,2,1", 1).unwrap()[0];
+    let arrangements = row
+        .arrangements()
+        .map(|r| {
+            r.conditions
+                .iter()
+                .map(|c| format!("{}", c))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        arrangements,
+        vec![
+            ".###....##.#",
+            ".###...##..#",
+            ".###...##.#.",
+            ".###..##...#",
+            ".###..##..#.",
+            ".###..##.#..",
+            ".###.##....#",
+            ".###.##...#.",
+            ".###.##..#..",
+            ".###.##.#...",
+        ]
+    );
+    assert_eq!(row.nth_arrangement(3), row.arrangements().nth(3));
+    assert_eq!(row.nth_arrangement(10), None);
+}
+
+#[test]
+fn arrangements_are_valid() {
+    let _ = env_logger::try_init();
+    for row in parse_and_expand_input(self::EXAMPLE_INPUT_1, 2).unwrap() {
+        let mut n = 0;
+        for arrangement in row.arrangements() {
+            n += 1;
+            assert!(row
+                .conditions
+                .iter()
+                .zip(arrangement.conditions.iter())
+                .all(|(a, b)| (a == &Condition::Unknown) | (a == b)));
+            let groups = arrangement
+                .conditions
+                .split(|c| c == &Condition::Operational)
+                .map(|g| g.len())
+                .filter(|l| *l > 0)
+                .collect::<Vec<_>>();
+            assert_eq!(groups, row.groups);
+            assert_eq!(arrangement.count_arrangements(), 1);
+        }
+        assert_eq!(n as u128, row.count_arrangements());
+    }
+}
@@ -1,4 +1,7 @@
-use aoc_2023::solutions::day13::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day13::{
+    parse_grid, parse_input, puzzle_1, puzzle_2, reflections, reflections_with_smudges,
+    Orientation, PuzzleErr, Reflection, Smudge,
+};
 
 const EXAMPLE_INPUT_1: &str = "
 #.##..##.
@@ -29,3 +32,82 @@ fn puzzle_2_example_1() {
     let _ = env_logger::try_init();
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(400));
 }
+
+#[test]
+fn reflections_example_1() {
+    let _ = env_logger::try_init();
+    let grids = parse_input(self::EXAMPLE_INPUT_1).unwrap();
+    let all = reflections(&grids[0]);
+    assert_eq!(all.len(), 6 + 8);
+    assert_eq!(
+        all.iter()
+            .filter(|r| r.orientation == Orientation::Vertical)
+            .map(|r| r.smudges.len())
+            .min(),
+        Some(0)
+    );
+
+    let perfect = reflections_with_smudges(&grids[0], 0);
+    assert_eq!(perfect.len(), 1);
+    assert_eq!(
+        (perfect[0].orientation, perfect[0].position),
+        (Orientation::Vertical, 5)
+    );
+
+    let smudged = reflections_with_smudges(&grids[0], 1);
+    assert_eq!(
+        smudged,
+        vec![Reflection {
+            orientation: Orientation::Horizontal,
+            position: 3,
+            smudges: vec![Smudge {
+                cell: (0, 0),
+                mirror: (5, 0)
+            }],
+        }]
+    );
+    assert_eq!(smudged[0].summary(), 300);
+    assert_eq!(
+        format!("{}", smudged[0]),
+        "horizontal line after 3 rows with 1 smudge(s)\n  fix (0, 0) or its mirror (5, 0)"
+    );
+
+    let smudged = reflections_with_smudges(&grids[1], 1);
+    assert_eq!(smudged.len(), 1);
+    assert_eq!(smudged[0].position, 1);
+    assert_eq!(
+        smudged[0].smudges,
+        vec![Smudge {
+            cell: (0, 4),
+            mirror: (1, 4)
+        }]
+    );
+}
+
+#[test]
+fn vertical_smudge_coordinates() {
+    let _ = env_logger::try_init();
+    let grid = parse_grid("#..#\n.##.\n#.##").unwrap();
+    let smudged = reflections_with_smudges(&grid, 1);
+    assert_eq!(
+        smudged,
+        vec![Reflection {
+            orientation: Orientation::Vertical,
+            position: 2,
+            smudges: vec![Smudge {
+                cell: (2, 1),
+                mirror: (2, 2)
+            }],
+        }]
+    );
+    assert_eq!(reflections_with_smudges(&grid, 2).len(), 1);
+}
+
+#[test]
+fn no_reflection() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        puzzle_1("#.\n..\n\n#.\n.#"),
+        Err(PuzzleErr::NoReflection(0))
+    );
+}
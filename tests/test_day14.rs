@@ -1,4 +1,7 @@
-use aoc_2023::solutions::day14::{puzzle_1, puzzle_2};
+use aoc_2023::memo::CacheStats;
+use aoc_2023::solutions::day14::{
+    puzzle_1, puzzle_2, spin_history, Direction, Platform, PuzzleErr, SpinCycler, SpinHistory,
+};
 
 const EXAMPLE_INPUT_1: &str = "
 O....#....
@@ -16,11 +19,151 @@ O.#..O.#.#
 #[test]
 fn puzzle_1_example_1() {
     let _ = env_logger::try_init();
-    assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1), 136);
+    assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1), Ok(136));
 }
 
 #[test]
 fn puzzle_2_example_1() {
     let _ = env_logger::try_init();
-    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1, 1000000000), 64);
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1, 1000000000), Ok(64));
+}
+
+#[test]
+fn tilt_each_direction() {
+    let _ = env_logger::try_init();
+    let platform = Platform::parse("O.#O\n.O.O\nO#..\n..O.").unwrap();
+    let tilted = |dir| {
+        let mut p = platform.clone();
+        p.tilt(dir);
+        format!("{}", p)
+    };
+    assert_eq!(tilted(Direction::North), "OO#O\nO.OO\n.#..\n....");
+    assert_eq!(tilted(Direction::South), "..#.\n.O..\nO#.O\nO.OO");
+    assert_eq!(tilted(Direction::West), "O.#O\nOO..\nO#..\nO...");
+    assert_eq!(tilted(Direction::East), ".O#O\n..OO\nO#..\n...O");
+}
+
+const EXAMPLE_AFTER_CYCLES: [&str; 3] = [
+    "
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+",
+    "
+.....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#..OO###..
+#.OOO#...O
+",
+    "
+.....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O
+",
+];
+
+#[test]
+fn spin_cycles_example_1() {
+    let _ = env_logger::try_init();
+    let mut platform = Platform::parse(self::EXAMPLE_INPUT_1).unwrap();
+    for expected in EXAMPLE_AFTER_CYCLES {
+        platform.spin_cycle();
+        assert_eq!(format!("{}", platform), expected.trim());
+    }
+}
+
+#[test]
+fn spin_history_example_1() {
+    let _ = env_logger::try_init();
+    let history = SpinHistory::new(Platform::parse(self::EXAMPLE_INPUT_1).unwrap());
+    assert_eq!((history.loop_start, history.period), (3, 7));
+    assert_eq!(history.loads.len(), 10);
+    assert_eq!(
+        format!("{}", history.platform_at(3 + 7 * 1000)),
+        EXAMPLE_AFTER_CYCLES[2].trim()
+    );
+    let mut platform = Platform::parse(self::EXAMPLE_INPUT_1).unwrap();
+    for n in 0..30 {
+        assert_eq!(history.load_at(n), platform.north_load());
+        assert_eq!(history.platform_at(n).fingerprint(), platform.fingerprint());
+        platform.spin_cycle();
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_1("O.\n.X"), Err(PuzzleErr::ParseInputError));
+    assert_eq!(puzzle_1("O.\n..."), Err(PuzzleErr::ParseInputError));
+    assert_eq!(puzzle_1(&".".repeat(129)), Err(PuzzleErr::TooWide));
+}
+
+#[test]
+fn spin_history_report_example_1() {
+    let _ = env_logger::try_init();
+    let history = spin_history(self::EXAMPLE_INPUT_1).unwrap();
+    assert_eq!(history.equivalent_cycle(2), 2);
+    assert_eq!(history.equivalent_cycle(10), 3);
+    assert_eq!(history.equivalent_cycle(1000000000), 6);
+    let expected = "
+Pre-period: 3 cycles
+Period: 7 cycles
+cycle  load
+    0  104
+    1  87
+    2  69
+    3  69  <- loop start
+    4  69
+    5  65
+    6  64
+    7  65
+    8  63
+    9  68
+";
+    assert_eq!(format!("{}", history), expected.trim_start());
+}
+
+#[test]
+fn spin_cycler_is_scoped_and_resettable() {
+    let _ = env_logger::try_init();
+    let platform = Platform::parse(self::EXAMPLE_INPUT_1).unwrap();
+    let mut cycler = SpinCycler::new();
+    let history = cycler.history(platform.clone());
+    assert_eq!(history.load_at(1000000000), 64);
+    let first_run = cycler.stats();
+    assert!(first_run.hits > 0 && first_run.misses > 0);
+
+    // A second run reuses every row slide computed by the first.
+    let again = cycler.history(platform.clone());
+    assert_eq!(again.load_at(1000000000), 64);
+    assert_eq!(cycler.stats().misses, first_run.misses);
+    assert!(cycler.stats().hits > first_run.hits);
+
+    cycler.clear();
+    assert_eq!(cycler.stats(), CacheStats::default());
+    let mut expected = platform.clone();
+    expected.spin_cycle();
+    let mut cycled = platform.clone();
+    cycler.spin_cycle(&mut cycled);
+    assert_eq!(cycled, expected);
 }
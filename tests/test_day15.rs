@@ -1,4 +1,8 @@
-use aoc_2023::solutions::day15::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day15::{
+    ascii_hash, focusing_power, hash_sum, parse_steps, puzzle_1, puzzle_2, trace, LensBoxes,
+    Operation, PuzzleErr, Step,
+};
+use std::io::Read;
 
 const EXAMPLE_INPUT_1: &str = "HASH";
 const EXAMPLE_INPUT_2: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
@@ -17,5 +21,226 @@ fn puzzle_1_example_2() {
 #[test]
 fn puzzle_2_example_1() {
     let _ = env_logger::try_init();
-    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_2), 145);
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_2), Ok(145));
+}
+
+const EXAMPLE_TRACE: &str = r#"
+After "rn=1":
+Box 0: [rn 1]
+
+After "cm-":
+Box 0: [rn 1]
+
+After "qp=3":
+Box 0: [rn 1]
+Box 1: [qp 3]
+
+After "cm=2":
+Box 0: [rn 1] [cm 2]
+Box 1: [qp 3]
+
+After "qp-":
+Box 0: [rn 1] [cm 2]
+
+After "pc=4":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4]
+
+After "ot=9":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9]
+
+After "ab=5":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9] [ab 5]
+
+After "pc-":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5]
+
+After "pc=6":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5] [pc 6]
+
+After "ot=7":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 7] [ab 5] [pc 6]
+"#;
+
+#[test]
+fn trace_example_2() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        trace(self::EXAMPLE_INPUT_2, LensBoxes::default()),
+        Ok(EXAMPLE_TRACE.trim().to_string())
+    );
+}
+
+#[test]
+fn states_example_2() {
+    let _ = env_logger::try_init();
+    let powers = LensBoxes::default()
+        .states(parse_steps(self::EXAMPLE_INPUT_2.as_bytes()))
+        .map(|s| s.unwrap().1.focusing_power())
+        .collect::<Vec<_>>();
+    assert_eq!(powers.len(), 11);
+    assert_eq!(powers[0], 1);
+    assert_eq!(powers[10], 145);
+}
+
+#[test]
+fn configurable_boxes() {
+    let _ = env_logger::try_init();
+    let mut boxes = LensBoxes::new(4, ascii_hash).unwrap();
+    assert_eq!(boxes.box_index("qp"), 1);
+    for step in parse_steps(self::EXAMPLE_INPUT_2.as_bytes()) {
+        boxes.apply(&step.unwrap());
+    }
+    assert_eq!(boxes.focusing_power(), 145);
+
+    let mut boxes = LensBoxes::new(3, |label| label.len()).unwrap();
+    for step in parse_steps(self::EXAMPLE_INPUT_2.as_bytes()) {
+        boxes.apply(&step.unwrap());
+    }
+    assert_eq!(
+        format!("{}", boxes),
+        "Box 2: [rn 1] [cm 2] [ot 7] [ab 5] [pc 6]"
+    );
+    assert_eq!(boxes.focusing_power(), 228);
+
+    assert!(matches!(
+        LensBoxes::new(0, ascii_hash),
+        Err(PuzzleErr::NoBoxes)
+    ));
+}
+
+#[test]
+fn step_parsing() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        Step::try_from("ab=5"),
+        Ok(Step {
+            label: "ab".to_string(),
+            op: Operation::Insert(5)
+        })
+    );
+    assert_eq!(
+        Step::try_from("ab*5"),
+        Err(PuzzleErr::UnknownOperation("ab*5".to_string()))
+    );
+    assert!(matches!(
+        puzzle_2("rn=1,ab=x"),
+        Err(PuzzleErr::ParseIntError(_))
+    ));
+}
+
+// Generates `n_steps` pseudo-random steps on demand, wrapping lines like the puzzle input.
+struct SyntheticSequence {
+    n_steps: usize,
+    state: u64,
+    pending: Vec<u8>,
+}
+
+impl SyntheticSequence {
+    fn new(n_steps: usize) -> Self {
+        Self {
+            n_steps,
+            state: 42,
+            pending: Vec::new(),
+        }
+    }
+
+    fn next_step(&mut self) -> String {
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        let x = self.state >> 33;
+        let label = format!("{}{}", (b'a' + (x % 26) as u8) as char, x % 1000);
+        let step = match x % 3 {
+            0 => format!("{}-", label),
+            _ => format!("{}={}", label, 1 + x % 9),
+        };
+        self.n_steps -= 1;
+        match (self.n_steps, self.n_steps % 50) {
+            (0, _) => step,
+            (_, 0) => format!("{},\n", step),
+            _ => format!("{},", step),
+        }
+    }
+}
+
+impl Read for SyntheticSequence {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pending.len() < buf.len() && self.n_steps > 0 {
+            let step = self.next_step();
+            self.pending.extend(step.bytes());
+        }
+        let n = buf.len().min(self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+}
+
+#[test]
+fn streaming_matches_in_memory() {
+    let _ = env_logger::try_init();
+    let mut input = String::new();
+    SyntheticSequence::new(10_000)
+        .read_to_string(&mut input)
+        .unwrap();
+    assert_eq!(input.lines().count(), 200);
+    assert_eq!(
+        hash_sum(SyntheticSequence::new(10_000)),
+        Ok(input.replace('\n', "").split(',').map(ascii_hash).sum())
+    );
+    let mut boxes = LensBoxes::default();
+    for step in input.replace('\n', "").split(',') {
+        boxes.apply(&Step::try_from(step).unwrap());
+    }
+    assert_eq!(
+        focusing_power(SyntheticSequence::new(10_000), LensBoxes::default()),
+        Ok(boxes.focusing_power())
+    );
+}
+
+#[test]
+fn streaming_long_sequence() {
+    let _ = env_logger::try_init();
+    let n_steps = 500_000;
+    assert_eq!(
+        parse_steps(SyntheticSequence::new(n_steps)).count(),
+        n_steps
+    );
+    assert!(focusing_power(SyntheticSequence::new(n_steps), LensBoxes::default()).is_ok());
+}
+
+#[test]
+fn streaming_ignores_newlines() {
+    let _ = env_logger::try_init();
+    let wrapped = "rn=1,cm-,qp=3,c\nm=2,qp-,pc=4,\not=9,ab=5,pc-,pc=6,ot=7\n";
+    assert_eq!(hash_sum(wrapped.as_bytes()), Ok(1320));
+    assert_eq!(
+        focusing_power(wrapped.as_bytes(), LensBoxes::default()),
+        Ok(145)
+    );
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("disk on fire"))
+    }
+}
+
+#[test]
+fn streaming_read_errors() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        hash_sum(FailingReader),
+        Err(PuzzleErr::ReadError("disk on fire".to_string()))
+    );
+    assert_eq!(
+        focusing_power(FailingReader, LensBoxes::default()),
+        Err(PuzzleErr::ReadError("disk on fire".to_string()))
+    );
 }
@@ -1,4 +1,7 @@
-use aoc_2023::solutions::day16::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day16::{
+    count_energized_tiles, energized_heatmap, max_energized_tiles, puzzle_1, puzzle_2,
+    ElementTable, PuzzleErr,
+};
 
 const EXAMPLE_INPUT_1: &str = r"
 .|...\....
@@ -24,3 +27,107 @@ fn puzzle_2_example_1() {
     let _ = env_logger::try_init();
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(51));
 }
+
+const EXAMPLE_1_HEATMAP: &str = "
+7,18,19,18,20,20,3,4,6,4
+6,21,3,4,5,20,3,4,6,4
+7,18,2,2,4,18,18,20,22,20
+7,19,2,2,4,19,19,3,4,3
+8,20,3,3,5,20,20,4,6,4
+8,20,3,3,5,20,20,4,6,4
+6,21,3,4,21,18,18,20,6,4
+24,18,19,18,18,18,18,18,5,3
+7,18,19,18,18,18,18,18,5,3
+6,19,2,2,2,18,2,18,5,2
+";
+
+#[test]
+fn heatmap_example_1() {
+    let _ = env_logger::try_init();
+    let heatmap = energized_heatmap(self::EXAMPLE_INPUT_1, &ElementTable::default()).unwrap();
+    assert_eq!(heatmap.to_csv(), self::EXAMPLE_1_HEATMAP.trim());
+    assert!(heatmap.to_pgm().starts_with("P2\n10 10\n24\n7 18 19"));
+}
+
+// A serpentine of mirrors, so a single beam passes through every tile in turn.
+fn serpentine(n: usize) -> String {
+    (0..n)
+        .map(|r| {
+            let (first, last) = match r % 2 {
+                0 if r == 0 => ('.', '\\'),
+                0 => ('\\', '\\'),
+                _ => ('/', '/'),
+            };
+            let mut row = String::from(first);
+            row.push_str(&".".repeat(n - 2));
+            row.push(last);
+            row
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn puzzle_1_long_beam() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_1(&serpentine(300)), Ok(300 * 300));
+}
+
+const CUSTOM_ELEMENTS: &str = r"
+. U:U D:D L:L R:R
+\ U:L D:R L:U R:D
+/ U:R D:L L:D R:U
+| U:U D:D L:UD R:UD
+- U:LR D:LR L:L R:R
+// absorbs every beam
+X
+// only lets beams through travelling right
+> R:R
++ U:ULR D:DLR L:LUD R:RUD
+";
+
+const CUSTOM_INPUT: &str = r"
+..+..\....
+.X..>.....
+..>..+....
+....X.....
+..........
+";
+
+#[test]
+fn custom_elements() {
+    let _ = env_logger::try_init();
+    let elements = ElementTable::parse(self::CUSTOM_ELEMENTS).unwrap();
+    assert_eq!(count_energized_tiles(self::CUSTOM_INPUT, &elements), Ok(18));
+    assert_eq!(max_energized_tiles(self::CUSTOM_INPUT, &elements), Ok(20));
+    assert_eq!(
+        count_energized_tiles(self::EXAMPLE_INPUT_1, &elements),
+        Ok(46)
+    );
+}
+
+#[test]
+fn custom_wall_element() {
+    let _ = env_logger::try_init();
+    let config = format!("{}\n// walls absorb every beam\n#", self::CUSTOM_ELEMENTS);
+    let elements = ElementTable::parse(&config).unwrap();
+    assert_eq!(count_energized_tiles("..#..", &elements), Ok(3));
+    assert_eq!(count_energized_tiles(".\\#\n.-.", &elements), Ok(5));
+}
+
+#[test]
+fn custom_elements_errors() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        ElementTable::parse("| U:Q"),
+        Err(PuzzleErr::ConfigError("Q".to_string()))
+    );
+    assert_eq!(
+        ElementTable::parse("|| U:U"),
+        Err(PuzzleErr::ConfigError("|| U:U".to_string()))
+    );
+    assert_eq!(
+        puzzle_1(self::CUSTOM_INPUT),
+        Err(PuzzleErr::ParseInputError("+".to_string()))
+    );
+}
@@ -1,4 +1,4 @@
-use aoc_2023::solutions::day17::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day17::{crucible_path, puzzle_1, puzzle_2, render_path};
 
 const EXAMPLE_INPUT_1: &str = "
 2413432311323
@@ -28,6 +28,35 @@ fn puzzle_2_example_1() {
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(94));
 }
 
+const EXAMPLE_1_ROUTE_1: &str = "
+2>>34^>>>1323
+32v>>>35v5623
+32552456v>>54
+3446585845v52
+4546657867v>6
+14385987984v4
+44578769877v6
+36378779796v>
+465496798688v
+456467998645v
+12246868655<v
+25465488877v5
+43226746555v>
+";
+
+#[test]
+fn puzzle_1_example_1_route() {
+    let _ = env_logger::try_init();
+    let path = crucible_path(self::EXAMPLE_INPUT_1, 1, 3).unwrap();
+    assert_eq!(path.heat_loss, 102);
+    assert_eq!(path.steps.len(), 28);
+    assert_eq!(path.turns.len(), 14);
+    assert_eq!(
+        render_path(self::EXAMPLE_INPUT_1, &path),
+        Ok(self::EXAMPLE_1_ROUTE_1.trim().to_string())
+    );
+}
+
 const EXAMPLE_INPUT_2: &str = "
 111111111111
 999999999991
@@ -41,3 +70,35 @@ fn puzzle_2_example_2() {
     let _ = env_logger::try_init();
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_2), Ok(71));
 }
+
+const EXAMPLE_2_ROUTE_2: &str = "
+1>>>>>>>1111
+9999999v9991
+9999999v9991
+9999999v9991
+9999999v>>>>
+";
+
+#[test]
+fn puzzle_2_example_2_route() {
+    let _ = env_logger::try_init();
+    let path = crucible_path(self::EXAMPLE_INPUT_2, 4, 10).unwrap();
+    assert_eq!(path.heat_loss, 71);
+    assert_eq!(path.turns.len(), 2);
+    assert_eq!(
+        render_path(self::EXAMPLE_INPUT_2, &path),
+        Ok(self::EXAMPLE_2_ROUTE_2.trim().to_string())
+    );
+}
+
+#[test]
+fn single_block() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_1("5"), Ok(0));
+    assert_eq!(puzzle_2("5"), Ok(0));
+    let path = crucible_path("1", 1, 3).unwrap();
+    assert_eq!(path.heat_loss, 0);
+    assert!(path.steps.is_empty());
+    assert!(path.turns.is_empty());
+    assert_eq!(render_path("1", &path), Ok("1".to_string()));
+}
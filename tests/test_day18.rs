@@ -1,4 +1,4 @@
-use aoc_2023::solutions::day18::{puzzle_1, puzzle_2};
+use aoc_2023::solutions::day18::{puzzle_1, puzzle_2, render, PuzzleErr, RenderFormat};
 
 const EXAMPLE_INPUT_1: &str = "
 R 6 (#70c710)
@@ -28,3 +28,70 @@ fn puzzle_2_example_1() {
     let _ = env_logger::try_init();
     assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(952408144115));
 }
+
+const EXAMPLE_1_LAGOON: &str = "
+#######
+#######
+#######
+..#####
+..#####
+#######
+#####..
+#######
+.######
+.######
+";
+
+#[test]
+fn render_example_1() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        render(self::EXAMPLE_INPUT_1, RenderFormat::Text),
+        Ok(EXAMPLE_1_LAGOON.trim().to_string())
+    );
+
+    let ansi = render(self::EXAMPLE_INPUT_1, RenderFormat::Ansi).unwrap();
+    // The start is dug by the last step (#7a21e3), the cell after it by the first (#70c710).
+    assert!(ansi.starts_with("\x1b[38;2;122;33;227m#\x1b[0m\x1b[38;2;112;199;16m#"));
+    assert_eq!(ansi.matches("\x1b[38;2;").count(), 38);
+
+    let svg = render(self::EXAMPLE_INPUT_1, RenderFormat::Svg).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>"));
+    assert_eq!(svg.matches("<line").count(), 14);
+    assert!(svg.contains(r##"stroke="#70c710""##));
+}
+
+#[test]
+fn invalid_plans() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        puzzle_1("R 2 (#000000)\nD 2 (#000000)"),
+        Err(PuzzleErr::UnclosedPlan((-2, 2)))
+    );
+    // A figure of eight whose first and fourth steps cross.
+    assert_eq!(
+        puzzle_1("R 4\nD 2\nL 2\nU 4\nL 2\nD 2"),
+        Err(PuzzleErr::SelfCrossing(0, 3))
+    );
+    // Digging straight back along the same trench.
+    assert_eq!(puzzle_1("R 2\nL 2"), Err(PuzzleErr::SelfCrossing(0, 1)));
+    assert_eq!(
+        render("R 2\nL 2", RenderFormat::Text),
+        Err(PuzzleErr::SelfCrossing(0, 1))
+    );
+    // Two loops that meet at a single corner.
+    assert_eq!(
+        puzzle_1("R 1\nD 1\nR 1\nD 1\nL 1\nU 1\nL 1\nU 1"),
+        Err(PuzzleErr::SelfCrossing(1, 5))
+    );
+}
+
+#[test]
+fn render_too_large() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        render("R 10000\nD 10000\nL 10000\nU 10000", RenderFormat::Text),
+        Err(PuzzleErr::TooLarge(100020001))
+    );
+}
@@ -1,4 +1,4 @@
-use aoc_2023::solutions::day19::puzzle_1;
+use aoc_2023::solutions::day19::{puzzle_1, puzzle_2};
 
 const EXAMPLE_INPUT_1: &str = "
 px{a<2006:qkq,m>2090:A,rfg}
@@ -26,8 +26,8 @@ fn puzzle_1_example_1() {
     assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1), Ok(19114));
 }
 
-// #[test]
-// fn puzzle_2_example_1() {
-//     let _ = env_logger::try_init();
-//     assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1), Ok(167409079868000));
-// }
+#[test]
+fn puzzle_2_example_1() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(167409079868000));
+}
@@ -1,4 +1,7 @@
-use aoc_2023::solutions::day21::puzzle_1;
+use aoc_2023::solutions::day21::{
+    distance_map, growth_settling, puzzle_1, puzzle_2, reachable_plots, reachable_series, Pos,
+    PuzzleErr, Tiling,
+};
 
 const EXAMPLE_INPUT_1: &str = "
 ...........
@@ -19,3 +22,105 @@ fn puzzle_1_example_1() {
     let _ = env_logger::try_init();
     assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1, 6), Ok(16));
 }
+
+#[test]
+fn reachable_plots_infinite_example_1() {
+    let _ = env_logger::try_init();
+    for (n_steps, expected) in [(6, 16), (10, 50), (50, 1594), (100, 6536), (500, 167004)] {
+        assert_eq!(
+            reachable_plots(self::EXAMPLE_INPUT_1, n_steps, Tiling::Infinite),
+            Ok(expected)
+        );
+    }
+}
+
+#[test]
+fn puzzle_2_example_1() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1, 1000), Ok(668697));
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1, 5000), Ok(16733044));
+}
+
+#[test]
+fn puzzle_2_matches_brute_force() {
+    let _ = env_logger::try_init();
+    for n_steps in [0, 7, 64, 131, 250, 301] {
+        assert_eq!(
+            puzzle_2(self::EXAMPLE_INPUT_1, n_steps),
+            reachable_plots(self::EXAMPLE_INPUT_1, n_steps as usize, Tiling::Infinite)
+        );
+    }
+}
+
+#[test]
+fn puzzle_2_far_extrapolation() {
+    let _ = env_logger::try_init();
+    // Every tile is a garden plot, so (n + 1)^2 plots are reachable.
+    assert_eq!(puzzle_2("S", 26501365), Ok(702322399865956));
+}
+
+#[test]
+fn puzzle_2_not_square() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_2("..S\n...", 100), Err(PuzzleErr::NotSquare));
+}
+
+#[test]
+fn distance_map_example_1() {
+    let _ = env_logger::try_init();
+    let dists = distance_map(self::EXAMPLE_INPUT_1, None, Tiling::Bounded).unwrap();
+    assert_eq!(dists.get(&Pos::new(5, 5)), Some(&0));
+    assert_eq!(dists.get(&Pos::new(0, 0)), Some(&10));
+    assert_eq!(dists.get(&Pos::new(1, 5)), None);
+    assert_eq!(
+        dists
+            .values()
+            .filter(|d| (**d <= 6) & (**d % 2 == 0))
+            .count(),
+        16
+    );
+}
+
+#[test]
+fn distance_map_infinite() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        distance_map(self::EXAMPLE_INPUT_1, None, Tiling::Infinite),
+        Err(PuzzleErr::Unbounded)
+    );
+    let dists = distance_map(self::EXAMPLE_INPUT_1, Some(10), Tiling::Infinite).unwrap();
+    assert_eq!(dists.values().filter(|d| **d % 2 == 0).count(), 50);
+}
+
+#[test]
+fn reachable_series_example_1() {
+    let _ = env_logger::try_init();
+    let series = reachable_series(self::EXAMPLE_INPUT_1, 10, Tiling::Infinite).unwrap();
+    assert_eq!(series[..4], [1, 2, 4, 6]);
+    assert_eq!(series[6], 16);
+    assert_eq!(series[10], 50);
+}
+
+#[test]
+fn growth_settling_example_1() {
+    let _ = env_logger::try_init();
+    let series = reachable_series(self::EXAMPLE_INPUT_1, 300, Tiling::Infinite).unwrap();
+    let settling = growth_settling(&series, 11).unwrap();
+    assert!(settling.step < 300 - 4 * 11);
+    assert_eq!(settling.extrapolate(&series, 100), Ok(6536));
+    assert_eq!(settling.extrapolate(&series, 500), Ok(167004));
+    assert_eq!(settling.extrapolate(&series, 1000), Ok(668697));
+    assert_eq!(settling.extrapolate(&series, 5000), Ok(16733044));
+
+    // Extrapolating from part of the series reproduces the rest of it.
+    let settling = growth_settling(&series[..200], 11).unwrap();
+    for n in 200..=300 {
+        assert_eq!(
+            settling.extrapolate(&series[..200], n),
+            Ok(series[n as usize])
+        );
+    }
+
+    // Too short a series to confirm the pattern.
+    assert_eq!(growth_settling(&series[..40], 11), None);
+}
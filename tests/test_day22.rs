@@ -1,4 +1,4 @@
-use aoc_2023::solutions::day22::puzzle_1;
+use aoc_2023::solutions::day22::{puzzle_1, puzzle_2, support_graph, PuzzleErr};
 
 const EXAMPLE_INPUT_1: &str = "
 1,0,1~1,2,1
@@ -13,5 +13,60 @@ const EXAMPLE_INPUT_1: &str = "
 #[test]
 fn puzzle_1_example_1() {
     let _ = env_logger::try_init();
-    assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1), 5);
+    assert_eq!(puzzle_1(self::EXAMPLE_INPUT_1), Ok(5));
+}
+
+#[test]
+fn puzzle_2_example_1() {
+    let _ = env_logger::try_init();
+    assert_eq!(puzzle_2(self::EXAMPLE_INPUT_1), Ok(7));
+}
+
+#[test]
+fn support_graph_example_1() {
+    let _ = env_logger::try_init();
+    let graph = support_graph(self::EXAMPLE_INPUT_1).unwrap();
+    assert_eq!(
+        graph.supports,
+        vec![
+            vec![1, 2],
+            vec![3, 4],
+            vec![3, 4],
+            vec![5],
+            vec![5],
+            vec![6],
+            vec![]
+        ]
+    );
+    assert_eq!(
+        graph.supported_by,
+        vec![
+            vec![],
+            vec![0],
+            vec![0],
+            vec![1, 2],
+            vec![1, 2],
+            vec![3, 4],
+            vec![5]
+        ]
+    );
+    // Brick G drops from z=8 to rest on F at z=5.
+    assert_eq!((graph.bricks[6].a.z, graph.bricks[6].b.z), (5, 6));
+    assert_eq!(
+        (0..7).map(|i| graph.chain_reaction(i)).collect::<Vec<_>>(),
+        vec![6, 0, 0, 0, 0, 1, 0]
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        puzzle_1("1,0,1-1,2,1"),
+        Err(PuzzleErr::ParseInputError("1,0,1-1,2,1".to_string()))
+    );
+    assert_eq!(
+        puzzle_1("1,0~1,2,1"),
+        Err(PuzzleErr::ParseInputError("1,0".to_string()))
+    );
 }
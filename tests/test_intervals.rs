@@ -0,0 +1,42 @@
+use aoc_2023::intervals::IntervalSet;
+
+#[test]
+fn from_ranges_merges_overlaps() {
+    let set = IntervalSet::from_ranges([5..8, 0..3, 2..4, 8..10, 12..12]);
+    assert_eq!(set.ranges(), &[0..4, 5..10]);
+    assert_eq!(set.len(), 9);
+    assert_eq!(set.min(), Some(0));
+    assert_eq!(set.max(), Some(9));
+    assert!(set.contains(&7));
+    assert!(!set.contains(&4));
+}
+
+#[test]
+fn set_operations() {
+    let a = IntervalSet::from_ranges([0..10, 20..30]);
+    let b = IntervalSet::from(5..25);
+    assert_eq!(a.union(&b), IntervalSet::from(0..30));
+    assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+    assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+    assert_eq!(b.difference(&a), IntervalSet::from(10..20));
+    assert!(a.intersection(&IntervalSet::new()).is_empty());
+}
+
+#[test]
+fn split_at_value() {
+    let set = IntervalSet::from(1_u64..4001);
+    let (below, above) = set.split_at(1351);
+    assert_eq!(below, IntervalSet::from(1..1351));
+    assert_eq!(above, IntervalSet::from(1351..4001));
+    assert_eq!(below.len() + above.len(), 4000);
+}
+
+#[test]
+fn map_ranges_piecewise() {
+    // Seed-to-soil map from the day 5 example.
+    let rules = [(98_u32..100, 50), (50..98, 52)];
+    let seeds = IntervalSet::from_ranges([79..93, 55..68, 97..102]);
+    let soil = seeds.map_ranges(&rules);
+    assert_eq!(soil.ranges(), &[50..52, 57..70, 81..95, 99..102]);
+    assert_eq!(soil.len(), seeds.len());
+}
@@ -0,0 +1,86 @@
+use aoc_2023::math_utils::{lcm, MathError, PolynomialSequence};
+use num::Rational64;
+
+#[test]
+fn lcm_of_values() {
+    assert_eq!(lcm(vec![4, 6, 10]), 60);
+}
+
+#[test]
+fn polynomial_degree_and_predictions() {
+    let _ = env_logger::try_init();
+    let seq: Vec<i64> = vec![10, 13, 16, 21, 30, 45];
+    let poly = PolynomialSequence::fit(&seq).unwrap();
+    assert_eq!(poly.degree(), 3);
+    assert_eq!(poly.predict_ahead(1).unwrap(), 68);
+    assert_eq!(poly.predict_behind(1).unwrap(), 5);
+    assert_eq!(poly.value_at(2).unwrap(), 16);
+}
+
+#[test]
+fn polynomial_far_prediction_i128() {
+    let _ = env_logger::try_init();
+    // Squares: n^2 for n = 0..4.
+    let seq: Vec<i128> = vec![0, 1, 4, 9, 16];
+    let poly = PolynomialSequence::fit(&seq).unwrap();
+    assert_eq!(poly.degree(), 2);
+    assert_eq!(
+        poly.value_at(1_000_000_000_000).unwrap(),
+        1_000_000_000_000_000_000_000_000
+    );
+    assert_eq!(poly.predict_behind(3).unwrap(), 9);
+}
+
+#[test]
+fn polynomial_over_rationals() {
+    let _ = env_logger::try_init();
+    // n / 2 for n = 0..3.
+    let seq = (0..4).map(|n| Rational64::new(n, 2)).collect::<Vec<_>>();
+    let poly = PolynomialSequence::fit(&seq).unwrap();
+    assert_eq!(poly.degree(), 1);
+    assert_eq!(poly.predict_ahead(3).unwrap(), Rational64::new(6, 2));
+}
+
+#[test]
+fn constant_and_zero_sequences() {
+    let _ = env_logger::try_init();
+    assert_eq!(PolynomialSequence::fit(&[7_i64, 7, 7]).unwrap().degree(), 0);
+    assert_eq!(
+        PolynomialSequence::fit(&[0_i64, 0])
+            .unwrap()
+            .predict_ahead(5)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn non_polynomial_sequences() {
+    let _ = env_logger::try_init();
+    assert_eq!(
+        PolynomialSequence::fit(&[1_i64, 2, 4, 8, 16]),
+        Err(MathError::NotPolynomial)
+    );
+    assert_eq!(
+        PolynomialSequence::<i64>::fit(&[]),
+        Err(MathError::EmptySequence)
+    );
+}
+
+#[test]
+fn polynomial_far_prediction_fits_type() {
+    let _ = env_logger::try_init();
+    // The answer fits in an i64 even though C(i, 3) would not.
+    let poly = PolynomialSequence::fit(&[0i64, 1, 4, 9]).unwrap();
+    assert_eq!(poly.value_at(100_000_000).unwrap(), 10_000_000_000_000_000);
+}
+
+#[test]
+fn polynomial_prediction_near_overflow() {
+    let _ = env_logger::try_init();
+    // C(n, 2), whose value at 4e9 fits in an i64 although n(n - 1) does not.
+    let poly = PolynomialSequence::fit(&[0i64, 0, 1, 3, 6]).unwrap();
+    assert_eq!(poly.value_at(4_000_000_000), Ok(7_999_999_998_000_000_000));
+    assert_eq!(poly.value_at(5_000_000_000), Err(MathError::Overflow));
+    assert_eq!(poly.value_at(-3), Ok(6));
+}
@@ -0,0 +1,18 @@
+use aoc_2023::memo::{CacheStats, Memo};
+
+#[test]
+fn memo_counts_hits_and_misses() {
+    let mut memo = Memo::<u32, u64>::new();
+    assert_eq!(memo.get(&3), None);
+    assert_eq!(memo.insert(3, 9), 9);
+    assert_eq!(memo.get(&3), Some(9));
+    assert_eq!(memo.get(&3), Some(9));
+    assert_eq!(memo.len(), 1);
+    assert_eq!(memo.stats(), CacheStats { hits: 2, misses: 1 });
+    assert_eq!(format!("{}", memo.stats()), "2 hits, 1 misses");
+
+    memo.clear();
+    assert!(memo.is_empty());
+    assert_eq!(memo.stats(), CacheStats::default());
+    assert_eq!(memo.get(&3), None);
+}
@@ -0,0 +1,87 @@
+use aoc_2023::search::{astar, bfs, bfs_distances, dijkstra, manhattan};
+
+const GRID: &str = "
+.....
+.###.
+...#.
+##.#.
+.....
+";
+
+fn open_cells(grid: &str) -> Vec<(i32, i32)> {
+    grid.trim()
+        .lines()
+        .enumerate()
+        .flat_map(|(r, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, x)| x == &'.')
+                .map(move |(c, _)| (r as i32, c as i32))
+        })
+        .collect()
+}
+
+fn neighbors(cells: &[(i32, i32)], p: &(i32, i32)) -> Vec<(i32, i32)> {
+    [(-1, 0), (1, 0), (0, -1), (0, 1)]
+        .iter()
+        .map(|(dr, dc)| (p.0 + dr, p.1 + dc))
+        .filter(|n| cells.contains(n))
+        .collect()
+}
+
+#[test]
+fn bfs_finds_shortest_path() {
+    let _ = env_logger::try_init();
+    let cells = open_cells(self::GRID);
+    let (steps, path) = bfs([(2, 0)], |p| neighbors(&cells, p), |p| p == &(4, 0)).unwrap();
+    assert_eq!(steps, 6);
+    assert_eq!(path.len(), 7);
+    assert_eq!(path.first(), Some(&(2, 0)));
+    assert_eq!(path.last(), Some(&(4, 0)));
+}
+
+#[test]
+fn weighted_searches_agree() {
+    let _ = env_logger::try_init();
+    let cells = open_cells(self::GRID);
+    let weighted = |p: &(i32, i32)| {
+        neighbors(&cells, p)
+            .into_iter()
+            .map(|n| (n, n.1 + 1))
+            .collect::<Vec<_>>()
+    };
+    let goal = (4, 4);
+    let d = dijkstra([(2, 0)], weighted, |p| p == &goal).unwrap();
+    let a = astar([(2, 0)], weighted, |p| manhattan(*p, goal), |p| p == &goal).unwrap();
+    assert_eq!(d.0, a.0);
+    assert_eq!(d.0, 20);
+    assert_eq!(d.1, a.1);
+}
+
+#[test]
+fn unreachable_goal_is_none() {
+    let _ = env_logger::try_init();
+    let cells = open_cells(self::GRID);
+    assert_eq!(
+        bfs([(0, 0)], |p| neighbors(&cells, p), |p| p == &(9, 9)),
+        None
+    );
+    assert_eq!(
+        dijkstra(
+            [(0, 0)],
+            |p| neighbors(&cells, p).into_iter().map(|n| (n, 1)),
+            |p| { p == &(9, 9) }
+        ),
+        None
+    );
+}
+
+#[test]
+fn bfs_distances_with_limit() {
+    let _ = env_logger::try_init();
+    let cells = open_cells(self::GRID);
+    let dists = bfs_distances([(2, 0)], |p| neighbors(&cells, p), Some(2));
+    assert_eq!(dists.len(), 5);
+    assert_eq!(dists.get(&(3, 2)), None);
+    assert_eq!(dists.get(&(2, 2)), Some(&2));
+}